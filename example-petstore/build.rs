@@ -1,4 +1,4 @@
-use oapi_rustgen::{Analyzer, ClientWriter, TypesWriter, ServerWriter};
+use oapi_rustgen::{Analyzer, ClientWriter, TypesWriter, ServerWriter, ValidationWriter};
 
 const JSON: &str = include_str!("./petstore-expanded.json");
 
@@ -19,11 +19,16 @@ fn main() {
         .expect("generation should work")
         .to_string()
         .expect("should be convertible to string");
+    let validation_tokens = ValidationWriter::new(&analysis)
+        .write()
+        .expect("generation should work")
+        .to_string()
+        .expect("should be convertible to string");
 
     let client = format!("{}\n\n{}", types_tokens, client_tokens);
     let client = rustfmt_wrapper::rustfmt(client)
         .expect("should format");
-    let server = format!("{}\n\n{}", types_tokens, server_tokens);
+    let server = format!("{}\n\n{}\n\n{}", types_tokens, validation_tokens, server_tokens);
     let server = rustfmt_wrapper::rustfmt(server)
         .expect("should format");
 
@@ -1,14 +1,15 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use convert_case::{Case, Casing};
 use derive_more::{Display, Error};
 use http::Method;
 use jsonptr::{Pointer, Resolve, Token};
+use serde_json::Value;
 
 use crate::{
     join_ptr,
-    spec::{MediaType, ObjectOrReference, ParameterLocation, Schema, SchemaType, Spec},
-    DefaultRenamer, Renamer,
+    spec::{AdditionalProperties, MediaType, ObjectOrReference, ParameterLocation, Schema, SchemaType, Spec},
+    Bundler, BundlerError, DefaultRenamer, FormatMap, MediaTypeRegistry, Renamer,
 };
 
 #[derive(Debug, Clone)]
@@ -16,6 +17,7 @@ pub struct CollectedSchema {
     location: Pointer,
     name: String,
     schema: Schema,
+    discriminator: Option<DiscriminatorDef>,
 }
 
 impl CollectedSchema {
@@ -30,6 +32,54 @@ impl CollectedSchema {
     pub fn schema(&self) -> &Schema {
         &self.schema
     }
+
+    pub fn discriminator(&self) -> Option<&DiscriminatorDef> {
+        self.discriminator.as_ref()
+    }
+}
+
+/// A `oneOf` branch resolved to its variant name and the wire value the discriminator
+/// property carries for it.
+#[derive(Debug, Clone)]
+pub struct DiscriminatorVariant {
+    tag_value: String,
+    variant_ident: String,
+    type_name: String,
+}
+
+impl DiscriminatorVariant {
+    pub fn tag_value(&self) -> &str {
+        &self.tag_value
+    }
+
+    /// The Rust enum variant identifier, Pascal-cased from `tag_value`. Differs from
+    /// `tag_value` whenever the discriminator mapping key isn't already a valid, idiomatic
+    /// Rust identifier (e.g. `"dog-v2"`), in which case a `#[serde(rename = ..)]` is needed.
+    pub fn variant_ident(&self) -> &str {
+        &self.variant_ident
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+/// Metadata needed to emit an internally-tagged enum for a `oneOf` schema that carries an
+/// OpenAPI `discriminator`, modeled on paperclip's `ObjectVariant`.
+#[derive(Debug, Clone)]
+pub struct DiscriminatorDef {
+    property_name: String,
+    variants: Vec<DiscriminatorVariant>,
+}
+
+impl DiscriminatorDef {
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+
+    pub fn variants(&self) -> &[DiscriminatorVariant] {
+        &self.variants
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -66,6 +116,9 @@ pub struct ParameterDef {
     name: String,
     location: ParameterLocation,
     schema_type: String,
+    /// How an array-typed parameter is serialized onto the wire; `None` for non-array parameters.
+    collection_format: Option<CollectionFormat>,
+    required: bool,
 }
 
 impl ParameterDef {
@@ -80,6 +133,183 @@ impl ParameterDef {
     pub fn location(&self) -> ParameterLocation {
         self.location
     }
+
+    pub fn collection_format(&self) -> Option<CollectionFormat> {
+        self.collection_format
+    }
+
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    /// `schema_type()`, wrapped in `Option<..>` unless the parameter is required.
+    pub fn argument_type(&self) -> String {
+        if self.required {
+            self.schema_type.clone()
+        } else {
+            format!("Option<{}>", self.schema_type)
+        }
+    }
+}
+
+/// How an array-typed parameter's values are joined on the wire, following paperclip's
+/// `CollectionFormat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionFormat {
+    /// `?a=1&a=2` (OpenAPI 3 `style: form, explode: true`; 2.0 `collectionFormat: multi`)
+    Multi,
+    /// `?a=1,2` (`style: form, explode: false`; 2.0 `csv`)
+    Csv,
+    /// `?a=1 2` (`style: spaceDelimited`; 2.0 `ssv`)
+    Ssv,
+    /// `?a=1|2` (`style: pipeDelimited`; 2.0 `pipes`)
+    Pipes,
+    /// `?a=1\t2` (2.0 `tsv`, no OpenAPI 3 equivalent)
+    Tsv,
+}
+
+fn collection_format_for(
+    style: Option<&str>,
+    explode: Option<bool>,
+    collection_format: Option<&str>,
+    location: ParameterLocation,
+) -> CollectionFormat {
+    if let Some(collection_format) = collection_format {
+        return match collection_format {
+            "csv" => CollectionFormat::Csv,
+            "ssv" => CollectionFormat::Ssv,
+            "pipes" => CollectionFormat::Pipes,
+            "tsv" => CollectionFormat::Tsv,
+            "multi" => CollectionFormat::Multi,
+            _ => CollectionFormat::Csv,
+        };
+    }
+
+    match style {
+        Some("spaceDelimited") => CollectionFormat::Ssv,
+        Some("pipeDelimited") => CollectionFormat::Pipes,
+        Some("form") | None => {
+            if explode.unwrap_or(location == ParameterLocation::Query) {
+                CollectionFormat::Multi
+            } else {
+                CollectionFormat::Csv
+            }
+        }
+        Some(_) => CollectionFormat::Csv,
+    }
+}
+
+/// What an authenticated request has to carry for a given `securitySchemes` entry. Only the
+/// shapes that can be extracted purely from the request (no OAuth2/OIDC redirect flow) are
+/// supported; anything else is dropped with a warning by [`security_scheme_kind_from_value`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecuritySchemeKind {
+    /// `type: http, scheme: bearer` — credential is the `Authorization: Bearer <token>` value.
+    Bearer,
+    /// `type: http, scheme: basic` — credential is the decoded `user:password` pair.
+    Basic,
+    /// `type: apiKey` — credential is a single value read from `location`/`name`.
+    ApiKey {
+        location: ParameterLocation,
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct SecuritySchemeDef {
+    name: String,
+    kind: SecuritySchemeKind,
+}
+
+impl SecuritySchemeDef {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> &SecuritySchemeKind {
+        &self.kind
+    }
+}
+
+/// Parses one `components.securitySchemes` entry. Returns `None` (and logs a warning) for
+/// `oauth2`/`openIdConnect` schemes and anything else this generator doesn't know how to turn
+/// into a request-level extractor.
+fn security_scheme_kind_from_value(name: &str, def: &serde_json::Value) -> Option<SecuritySchemeKind> {
+    match def.get("type").and_then(|v| v.as_str()) {
+        Some("http") => match def.get("scheme").and_then(|v| v.as_str()) {
+            Some(s) if s.eq_ignore_ascii_case("bearer") => Some(SecuritySchemeKind::Bearer),
+            Some(s) if s.eq_ignore_ascii_case("basic") => Some(SecuritySchemeKind::Basic),
+            scheme => {
+                log::warn!("unsupported http security scheme `{:?}` for `{}`", scheme, name);
+                None
+            }
+        },
+        Some("apiKey") => {
+            let location = match def.get("in").and_then(|v| v.as_str()) {
+                Some("header") => ParameterLocation::Header,
+                Some("query") => ParameterLocation::Query,
+                Some("cookie") => ParameterLocation::Cookie,
+                location => {
+                    log::warn!("unsupported apiKey location `{:?}` for `{}`", location, name);
+                    return None;
+                }
+            };
+            let key_name = def.get("name").and_then(|v| v.as_str())?.to_owned();
+            Some(SecuritySchemeKind::ApiKey {
+                location,
+                name: key_name,
+            })
+        }
+        kind => {
+            log::warn!("unsupported security scheme type `{:?}` for `{}`", kind, name);
+            None
+        }
+    }
+}
+
+/// Converts a response map key (`"200"`, `"2XX"`, or `"default"`) into a stable Rust enum
+/// variant identifier: `S` followed by the uppercased key for status codes and ranges (`"2xx"`
+/// becomes `S2XX`), and `Default` for the `default` key.
+pub fn response_variant_name(status_code: &str) -> String {
+    if status_code.eq_ignore_ascii_case("default") {
+        "Default".to_owned()
+    } else {
+        format!("S{}", status_code.to_uppercase())
+    }
+}
+
+/// Whether a response map key is a range (`"2XX"`) or the `default` fallback, neither of which
+/// pins down a single status code at generation time, so the concrete status has to be carried
+/// alongside the variant's body at runtime instead.
+pub fn is_dynamic_response_status(status_code: &str) -> bool {
+    status_code.eq_ignore_ascii_case("default") || response_status_range(status_code).is_some()
+}
+
+/// Parses a status-range key (e.g. `"2XX"`) into its inclusive numeric bounds.
+pub fn response_status_range(status_code: &str) -> Option<(u16, u16)> {
+    let upper = status_code.to_uppercase();
+    if upper.len() == 3 && upper.ends_with("XX") {
+        upper
+            .chars()
+            .next()
+            .and_then(|c| c.to_digit(10))
+            .map(|d| (d as u16 * 100, d as u16 * 100 + 99))
+    } else {
+        None
+    }
+}
+
+/// A status code to fall back on when a response key doesn't pin down a single number on its
+/// own (a range's lower bound, or 500 for `default`) and no runtime value is available to carry
+/// instead (e.g. the operation has exactly one response and doesn't return one).
+pub fn default_status_for(status_code: &str) -> u16 {
+    if let Some((lo, _)) = response_status_range(status_code) {
+        lo
+    } else if status_code.eq_ignore_ascii_case("default") {
+        500
+    } else {
+        status_code.parse().unwrap_or(500)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -88,10 +318,18 @@ pub struct OperationDef {
     method: Method,
     path: Vec<SegmentOrParameter>,
     parameters: BTreeMap<(String, ParameterLocation), ParameterDef>,
-    request_body: Option<String>,
+    /// `(content_type, rust_type)` of the request body, if any
+    request_body: Option<(String, String)>,
     /// Name of the enum containing all of the possible resposes
     response: String,
-    responses: BTreeMap<String, Option<String>>,
+    /// status code -> `(content_type, rust_type)` of that response's body, if any
+    responses: BTreeMap<String, Option<(String, String)>>,
+    /// Names (into [`AnalysisResult::security_schemes`]) of the schemes accepted for this
+    /// operation. Empty means no authentication is required. OpenAPI lets a `security`
+    /// requirement `AND` several schemes together inside one alternative; this collapses all
+    /// alternatives into a flat "satisfy any one of these schemes" list, which covers the
+    /// common case (a single scheme per alternative) without modeling the full boolean shape.
+    security: Vec<String>,
 }
 
 impl OperationDef {
@@ -126,6 +364,30 @@ impl OperationDef {
         )
     }
 
+    pub fn query_params(&self) -> Box<dyn Iterator<Item = &ParameterDef> + '_> {
+        Box::new(
+            self.parameters
+                .values()
+                .filter(|p| p.location() == ParameterLocation::Query),
+        )
+    }
+
+    pub fn header_params(&self) -> Box<dyn Iterator<Item = &ParameterDef> + '_> {
+        Box::new(
+            self.parameters
+                .values()
+                .filter(|p| p.location() == ParameterLocation::Header),
+        )
+    }
+
+    pub fn cookie_params(&self) -> Box<dyn Iterator<Item = &ParameterDef> + '_> {
+        Box::new(
+            self.parameters
+                .values()
+                .filter(|p| p.location() == ParameterLocation::Cookie),
+        )
+    }
+
     pub fn param_by_name(&self, name: &str, location: ParameterLocation) -> Option<&ParameterDef> {
         self.parameters
             .iter()
@@ -133,16 +395,46 @@ impl OperationDef {
             .map(|(_, p)| p)
     }
 
+    /// Names of the security schemes accepted for this operation; empty if it's public.
+    pub fn security(&self) -> &[String] {
+        &self.security
+    }
+
+    pub fn requires_auth(&self) -> bool {
+        !self.security.is_empty()
+    }
+
     pub fn request_body(&self) -> Option<&str> {
-        self.request_body.as_deref()
+        self.request_body.as_ref().map(|(_, ty)| ty.as_str())
+    }
+
+    /// The media type (e.g. `application/json`) the request body is carried as.
+    pub fn request_body_media_type(&self) -> Option<&str> {
+        self.request_body.as_ref().map(|(ct, _)| ct.as_str())
     }
 
     pub fn response(&self) -> &str {
         &self.response
     }
 
-    pub fn responses(&self) -> &BTreeMap<String, Option<String>> {
-        &self.responses
+    pub fn responses(&self) -> BTreeMap<String, Option<String>> {
+        self.responses
+            .iter()
+            .map(|(status_code, body)| {
+                (
+                    status_code.clone(),
+                    body.as_ref().map(|(_, ty)| ty.clone()),
+                )
+            })
+            .collect()
+    }
+
+    /// The media type (e.g. `application/json`) the named status code's body is carried as.
+    pub fn response_media_type(&self, status_code: &str) -> Option<&str> {
+        self.responses
+            .get(status_code)
+            .and_then(|v| v.as_ref())
+            .map(|(ct, _)| ct.as_str())
     }
 
     pub fn has_default_response(&self) -> bool {
@@ -154,10 +446,23 @@ impl OperationDef {
     pub fn has_any_response_body(&self) -> bool {
         self.responses.iter().any(|(_, v)| v.is_some())
     }
+
+    /// If this operation's single response is carried over a streaming media type (SSE,
+    /// ndjson, ...), the `(content_type, item_rust_type)` of each individual event.
+    pub fn streaming_response(&self) -> Option<(&str, &str)> {
+        if self.responses.len() != 1 {
+            return None;
+        }
+        let (content_type, item_type) = self.responses.values().next()?.as_ref()?;
+        crate::media_type::is_streaming_media_type(content_type)
+            .then(|| (content_type.as_str(), item_type.as_str()))
+    }
 }
 
 pub struct Analyzer {
     renamer: Box<dyn Renamer>,
+    media_types: MediaTypeRegistry,
+    formats: FormatMap,
 }
 
 impl Analyzer {
@@ -169,11 +474,59 @@ impl Analyzer {
         self.renamer = renamer;
     }
 
+    pub fn with_media_types(&mut self, media_types: MediaTypeRegistry) {
+        self.media_types = media_types;
+    }
+
+    pub fn with_formats(&mut self, formats: FormatMap) {
+        self.formats = formats;
+    }
+
+    /// Parses `spec` as either JSON or YAML, trying JSON first (since it is a strict subset
+    /// of the common serialization formats tools tend to emit) and falling back to YAML.
     pub fn run(self, spec: &str) -> Result<AnalysisResult, AnalysisError> {
-        let spec: Spec = serde_json::de::from_str(spec).map_err(AnalysisError::Deserialization)?;
+        let spec: Spec = match serde_json::de::from_str(spec) {
+            Ok(spec) => spec,
+            Err(json_err) => {
+                serde_yaml::from_str(spec).map_err(|yaml_err| AnalysisError::Deserialization {
+                    json: json_err,
+                    yaml: yaml_err,
+                })?
+            }
+        };
+        let schemas = collect_types_to_generate(&spec);
+        Ok(AnalysisResult {
+            renamer: self.renamer,
+            media_types: self.media_types,
+            formats: self.formats,
+            spec,
+            schemas,
+        })
+    }
+
+    /// Like [`run`](Self::run), but first bundles `spec` with a [`Bundler`] rooted at
+    /// `base_dir`, inlining any `$ref`s that point at sibling files (or http(s) URLs) so the
+    /// rest of the spec can be split across multiple documents.
+    pub fn run_bundled(self, spec: &str, base_dir: impl Into<PathBuf>) -> Result<AnalysisResult, AnalysisError> {
+        let value: Value = match serde_json::de::from_str(spec) {
+            Ok(value) => value,
+            Err(json_err) => {
+                serde_yaml::from_str(spec).map_err(|yaml_err| AnalysisError::Deserialization {
+                    json: json_err,
+                    yaml: yaml_err,
+                })?
+            }
+        };
+        let value = Bundler::new(base_dir)
+            .bundle(value)
+            .map_err(AnalysisError::Bundling)?;
+        let spec: Spec =
+            serde_json::from_value(value).map_err(AnalysisError::BundledSpecInvalid)?;
         let schemas = collect_types_to_generate(&spec);
         Ok(AnalysisResult {
             renamer: self.renamer,
+            media_types: self.media_types,
+            formats: self.formats,
             spec,
             schemas,
         })
@@ -184,12 +537,16 @@ impl Default for Analyzer {
     fn default() -> Self {
         Self {
             renamer: Box::<DefaultRenamer>::default(),
+            media_types: MediaTypeRegistry::default(),
+            formats: FormatMap::default(),
         }
     }
 }
 
 pub struct AnalysisResult {
     renamer: Box<dyn Renamer>,
+    media_types: MediaTypeRegistry,
+    formats: FormatMap,
     spec: Spec,
     schemas: Vec<CollectedSchema>,
 }
@@ -199,6 +556,14 @@ impl AnalysisResult {
         &self.renamer
     }
 
+    pub fn media_types(&self) -> &MediaTypeRegistry {
+        &self.media_types
+    }
+
+    pub fn formats(&self) -> &FormatMap {
+        &self.formats
+    }
+
     pub fn spec(&self) -> &Spec {
         &self.spec
     }
@@ -211,6 +576,29 @@ impl AnalysisResult {
         self.schemas().iter().find(|s| s.location() == ptr)
     }
 
+    /// Reads `components.securitySchemes` off the raw spec value (rather than a typed field,
+    /// since schemes vary more in shape than this crate's other component kinds) and keeps
+    /// only the ones that can be turned into a request-level extractor.
+    pub fn security_schemes(&self) -> BTreeMap<String, SecuritySchemeDef> {
+        let spec_value = serde_json::to_value(&self.spec).expect("schema should be serializable");
+        spec_value
+            .pointer("/components/securitySchemes")
+            .and_then(|v| v.as_object())
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, def)| {
+                let kind = security_scheme_kind_from_value(name, def)?;
+                Some((
+                    name.clone(),
+                    SecuritySchemeDef {
+                        name: name.clone(),
+                        kind,
+                    },
+                ))
+            })
+            .collect()
+    }
+
     pub fn name_type(&self, ptr: &Pointer, schema: &ObjectOrReference<Schema>) -> String {
         if let Some(ty) = self.find_schema(ptr) {
             return ty.name.clone();
@@ -229,6 +617,23 @@ impl AnalysisResult {
                 .unwrap_or_else(|| panic!("reference `{}` should exist as schema", ref_path))
                 .name()
                 .to_owned(),
+            // No unit test here: a fixture needs a `Schema` literal, and `crate::spec` (where
+            // `Schema`/`AdditionalProperties` are declared) isn't checked in, so there's no
+            // field list to build one against.
+            ObjectOrReference::Object(schema)
+                if schema.schema_type == Some(SchemaType::Object)
+                    && schema.properties.is_empty()
+                    && matches!(schema.additional_properties, Some(AdditionalProperties::Schema(_))) =>
+            {
+                let Some(AdditionalProperties::Schema(value_schema)) = &schema.additional_properties else {
+                    unreachable!("matched above")
+                };
+                let value_ptr = join_ptr!(ptr, "additionalProperties");
+                make_nullable(format!(
+                    "std::collections::HashMap<String, {}>",
+                    self.name_type(&value_ptr, value_schema)
+                ))
+            }
             ObjectOrReference::Object(schema) if schema.schema_type == Some(SchemaType::Object) => {
                 make_nullable(
                     self.find_schema(ptr)
@@ -248,6 +653,23 @@ impl AnalysisResult {
                     make_nullable("Vec<serde_json::Value>".to_owned())
                 }
             }
+            ObjectOrReference::Object(schema)
+                if matches!(
+                    schema.schema_type,
+                    Some(SchemaType::Integer) | Some(SchemaType::Number) | Some(SchemaType::String)
+                ) && schema
+                    .format
+                    .as_deref()
+                    .and_then(|f| self.formats.rust_type_for(f))
+                    .is_some() =>
+            {
+                let rust_type = schema
+                    .format
+                    .as_deref()
+                    .and_then(|f| self.formats.rust_type_for(f))
+                    .expect("checked in guard");
+                make_nullable(rust_type.to_owned())
+            }
             ObjectOrReference::Object(schema)
                 if schema.schema_type == Some(SchemaType::Integer) =>
             {
@@ -330,6 +752,20 @@ impl AnalysisResult {
                 .map(|(i, p_or_ref)| {
                     let ptr = join_ptr!(ptr, "parameters", i.to_string(), "schema");
                     let param = p_or_ref.resolve(&self.spec).expect("should be resolvable");
+                    let is_array = param
+                        .schema
+                        .as_ref()
+                        .is_some_and(|s| s.schema_type == Some(SchemaType::Array));
+                    let collection_format = is_array.then(|| {
+                        collection_format_for(
+                            param.style.as_deref(),
+                            param.explode,
+                            param.collection_format.as_deref(),
+                            param.location,
+                        )
+                    });
+                    let required =
+                        param.location == ParameterLocation::Path || param.required.unwrap_or(false);
                     let schema =
                         ObjectOrReference::Object(param.schema.expect("should have a schema"));
                     match p_or_ref {
@@ -339,6 +775,8 @@ impl AnalysisResult {
                                 name: self.renamer().name_parameter(&s.name),
                                 schema_type: self.name_type(&ptr, &schema),
                                 location: s.location,
+                                collection_format,
+                                required,
                             },
                         ),
                         ObjectOrReference::Ref { ref_path } => {
@@ -349,6 +787,8 @@ impl AnalysisResult {
                                     name: self.renamer().name_parameter(&param.name),
                                     schema_type: s.name().to_owned(),
                                     location: param.location,
+                                    collection_format,
+                                    required,
                                 },
                             )
                         }
@@ -356,22 +796,17 @@ impl AnalysisResult {
                 })
                 .collect();
             let request_body = operation.request_body.as_ref().and_then(|b| match b {
-                ObjectOrReference::Object(s) => {
-                    let ptr = join_ptr!(
-                        &ptr,
-                        "request_body",
-                        "content",
-                        "application/json",
-                        "schema"
-                    );
-                    s.content
-                        .get("application/json")
-                        .and_then(|v| v.schema.as_ref())
-                        .map(|schema| self.name_type(&ptr, schema))
-                }
+                ObjectOrReference::Object(s) => s.content.iter().next().map(|(content_type, mt)| {
+                    let ptr = join_ptr!(&ptr, "request_body", "content", content_type, "schema");
+                    let schema_type = mt.schema.as_ref().map(|schema| self.name_type(&ptr, schema));
+                    let rust_type = self
+                        .media_types
+                        .rust_type_for(content_type, schema_type.as_deref());
+                    (content_type.clone(), rust_type)
+                }),
                 ObjectOrReference::Ref { ref_path } => {
                     let s = self.find_schema(ref_path).unwrap_or_else(|| panic!("reference `{}` should exist as schema", ref_path));
-                    Some(s.name().to_owned())
+                    Some(("application/json".to_owned(), s.name().to_owned()))
                 }
             });
             let responses: BTreeMap<_, _> = operation
@@ -379,23 +814,28 @@ impl AnalysisResult {
                 .iter()
                 .map(|(status_code, r_or_ref)| match r_or_ref {
                     ObjectOrReference::Object(r) => {
-                        let ptr = join_ptr!(
-                            &ptr,
-                            "responses",
-                            status_code,
-                            "content",
-                            "application/json",
-                            "schema"
-                        );
-                        let s = r
-                            .content
-                            .get("application/json")
-                            .and_then(|v| v.schema.as_ref())
-                            .map(|schema| self.name_type(&ptr, schema));
-                        (status_code.clone(), s)
+                        let body = r.content.iter().next().map(|(content_type, mt)| {
+                            let ptr = join_ptr!(
+                                &ptr,
+                                "responses",
+                                status_code,
+                                "content",
+                                content_type,
+                                "schema"
+                            );
+                            let schema_type =
+                                mt.schema.as_ref().map(|schema| self.name_type(&ptr, schema));
+                            let rust_type = self
+                                .media_types
+                                .rust_type_for(content_type, schema_type.as_deref());
+                            (content_type.clone(), rust_type)
+                        });
+                        (status_code.clone(), body)
                     }
                     ObjectOrReference::Ref { ref_path } => {
-                        let s = self.find_schema(ref_path).map(|s| s.name.clone());
+                        let s = self
+                            .find_schema(ref_path)
+                            .map(|s| ("application/json".to_owned(), s.name.clone()));
                         (status_code.clone(), s)
                     }
                 })
@@ -407,12 +847,26 @@ impl AnalysisResult {
                     .expect("single item")
                     .1
                     .as_ref()
-                    .cloned()
+                    .map(|(_, ty)| ty.clone())
                     .unwrap_or_else(|| "()".to_owned())
             } else {
                 format!("{}Response", operation_name)
             };
 
+            let security_ptr = join_ptr!(&ptr, "security");
+            let security = spec_value
+                .pointer(security_ptr.as_str())
+                .or_else(|| spec_value.pointer("/security"))
+                .and_then(|v| v.as_array())
+                .map(|requirements| {
+                    requirements
+                        .iter()
+                        .filter_map(|requirement| requirement.as_object())
+                        .flat_map(|requirement| requirement.keys().cloned())
+                        .collect()
+                })
+                .unwrap_or_default();
+
             operations.push(OperationDef {
                 name: operation_name.to_case(Case::Snake),
                 method,
@@ -421,6 +875,7 @@ impl AnalysisResult {
                 request_body,
                 response,
                 responses,
+                security,
             });
         }
 
@@ -430,8 +885,22 @@ impl AnalysisResult {
 
 #[derive(Debug, Display, Error)]
 pub enum AnalysisError {
-    #[display(fmt = "Failed to deserialize openapi spec: {}", _0)]
-    Deserialization(serde_json::Error),
+    #[display(
+        fmt = "Failed to deserialize openapi spec as JSON ({}) or YAML ({})",
+        json,
+        yaml
+    )]
+    Deserialization {
+        json: serde_json::Error,
+        #[error(not(source))]
+        yaml: serde_yaml::Error,
+    },
+
+    #[display(fmt = "Failed to bundle external $refs into the spec: {}", _0)]
+    Bundling(BundlerError),
+
+    #[display(fmt = "Bundled spec no longer matches the expected shape: {}", _0)]
+    BundledSpecInvalid(serde_json::Error),
 }
 
 fn collect_initial_types_to_generate(spec: &Spec) -> Vec<Pointer> {
@@ -556,6 +1025,56 @@ fn collect_initial_types_from_media_types(
         .flat_map(|(m, s)| s.object().map(|o| (m, o)))
 }
 
+/// Resolves a `oneOf` schema's `discriminator` (if any) into the metadata needed to emit an
+/// internally-tagged enum: each branch's variant name and the wire value that selects it,
+/// taken from the discriminator `mapping` when present and falling back to the referenced
+/// schema's own name otherwise.
+fn build_discriminator(
+    renamer: &DefaultRenamer,
+    spec: &serde_json::Value,
+    type_ptr: &Pointer,
+    schema: &Schema,
+) -> Option<DiscriminatorDef> {
+    let discriminator = schema.discriminator.as_ref()?;
+
+    let variants = schema
+        .one_of
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| match entry {
+            ObjectOrReference::Ref { ref_path } => {
+                let ref_str = ref_path.as_str();
+                let mapped_value = discriminator
+                    .mapping
+                    .iter()
+                    .find(|(_, v)| v.trim_start_matches('#') == ref_str || v.as_str() == ref_str)
+                    .map(|(k, _)| k.clone());
+                let type_name = renamer.name_type(spec, ref_path);
+                let tag_value = mapped_value.unwrap_or_else(|| type_name.clone());
+                DiscriminatorVariant {
+                    variant_ident: tag_value.to_case(Case::Pascal),
+                    tag_value,
+                    type_name,
+                }
+            }
+            ObjectOrReference::Object(_) => {
+                let ptr = join_ptr!(type_ptr, "oneOf", i.to_string());
+                let type_name = renamer.name_type(spec, &ptr);
+                DiscriminatorVariant {
+                    tag_value: type_name.clone(),
+                    variant_ident: type_name.clone(),
+                    type_name,
+                }
+            }
+        })
+        .collect();
+
+    Some(DiscriminatorDef {
+        property_name: discriminator.property_name.clone(),
+        variants,
+    })
+}
+
 fn collect_types_to_generate(spec: &Spec) -> Vec<CollectedSchema> {
     let renamer = DefaultRenamer {};
     // Initialize types to check
@@ -572,10 +1091,12 @@ fn collect_types_to_generate(spec: &Spec) -> Vec<CollectedSchema> {
 
         match schema {
             ObjectOrReference::Object(schema) if !schema.any_of.is_empty() || !schema.all_of.is_empty() || !schema.one_of.is_empty() => {
+                let discriminator = build_discriminator(&renamer, &spec, &type_ptr, &schema);
                 collected_types.push(CollectedSchema {
                     location: type_ptr.clone(),
                     name: renamer.name_type(&spec, &type_ptr),
                     schema: schema.clone(),
+                    discriminator,
                 });
                 for (i, _) in schema.any_of.iter().enumerate() {
                     let ptr = join_ptr!(&type_ptr, "anyOf", i.to_string());
@@ -590,17 +1111,37 @@ fn collect_types_to_generate(spec: &Spec) -> Vec<CollectedSchema> {
                     types_to_check.push(ptr);
                 }
             },
+            ObjectOrReference::Object(schema)
+                if schema.schema_type == Some(SchemaType::Object)
+                    && schema.properties.is_empty()
+                    && matches!(schema.additional_properties, Some(AdditionalProperties::Schema(_))) =>
+            {
+                // A pure `additionalProperties: <schema>` dictionary is represented inline as a
+                // `HashMap<String, T>` by `name_type`, so it does not get its own named struct --
+                // only its value schema needs to be enqueued for generation.
+                if let Some(AdditionalProperties::Schema(value_schema)) = &schema.additional_properties {
+                    if let ObjectOrReference::Object(_) = value_schema.as_ref() {
+                        types_to_check.push(join_ptr!(&type_ptr, "additionalProperties"));
+                    }
+                }
+            }
             ObjectOrReference::Object(schema) if schema.schema_type == Some(SchemaType::Object) => {
                 collected_types.push(CollectedSchema {
                     location: type_ptr.clone(),
                     name: renamer.name_type(&spec, &type_ptr),
                     schema: schema.clone(),
+                    discriminator: None,
                 });
                 for (name, schema) in &schema.properties {
                     if let ObjectOrReference::Object(_) = schema {
                         types_to_check.push(join_ptr!(&type_ptr, "properties", name));
                     }
                 }
+                if let Some(AdditionalProperties::Schema(value_schema)) = &schema.additional_properties {
+                    if let ObjectOrReference::Object(_) = value_schema.as_ref() {
+                        types_to_check.push(join_ptr!(&type_ptr, "additionalProperties"));
+                    }
+                }
             }
             ObjectOrReference::Object(schema) if schema.schema_type == Some(SchemaType::Array) => {
                 if let Some(ObjectOrReference::Object(_)) = schema.items.as_deref() {
@@ -613,3 +1154,86 @@ fn collect_types_to_generate(spec: &Spec) -> Vec<CollectedSchema> {
 
     collected_types
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collection_format_prefers_an_explicit_collection_format() {
+        assert_eq!(
+            collection_format_for(Some("form"), Some(true), Some("csv"), ParameterLocation::Query),
+            CollectionFormat::Csv
+        );
+        assert_eq!(
+            collection_format_for(None, None, Some("tsv"), ParameterLocation::Query),
+            CollectionFormat::Tsv
+        );
+        assert_eq!(
+            collection_format_for(None, None, Some("bogus"), ParameterLocation::Query),
+            CollectionFormat::Csv
+        );
+    }
+
+    #[test]
+    fn collection_format_maps_openapi_3_styles() {
+        assert_eq!(
+            collection_format_for(Some("spaceDelimited"), None, None, ParameterLocation::Query),
+            CollectionFormat::Ssv
+        );
+        assert_eq!(
+            collection_format_for(Some("pipeDelimited"), None, None, ParameterLocation::Query),
+            CollectionFormat::Pipes
+        );
+    }
+
+    #[test]
+    fn collection_format_defaults_form_style_by_explode_and_location() {
+        assert_eq!(
+            collection_format_for(Some("form"), Some(true), None, ParameterLocation::Query),
+            CollectionFormat::Multi
+        );
+        assert_eq!(
+            collection_format_for(Some("form"), Some(false), None, ParameterLocation::Query),
+            CollectionFormat::Csv
+        );
+        // No explicit `explode`: query params default to exploded (`multi`), everything else to `csv`.
+        assert_eq!(
+            collection_format_for(None, None, None, ParameterLocation::Query),
+            CollectionFormat::Multi
+        );
+        assert_eq!(
+            collection_format_for(None, None, None, ParameterLocation::Header),
+            CollectionFormat::Csv
+        );
+    }
+
+    #[test]
+    fn response_status_range_parses_a_range_key() {
+        assert_eq!(response_status_range("2XX"), Some((200, 299)));
+        assert_eq!(response_status_range("4xx"), Some((400, 499)));
+        assert_eq!(response_status_range("5XX"), Some((500, 599)));
+    }
+
+    #[test]
+    fn response_status_range_rejects_non_range_keys() {
+        assert_eq!(response_status_range("200"), None);
+        assert_eq!(response_status_range("default"), None);
+        assert_eq!(response_status_range("XXX"), None);
+    }
+
+    #[test]
+    fn is_dynamic_response_status_covers_ranges_and_default() {
+        assert!(is_dynamic_response_status("default"));
+        assert!(is_dynamic_response_status("DEFAULT"));
+        assert!(is_dynamic_response_status("2XX"));
+        assert!(!is_dynamic_response_status("200"));
+    }
+
+    #[test]
+    fn response_variant_name_renames_default_and_uppercases_ranges() {
+        assert_eq!(response_variant_name("default"), "Default");
+        assert_eq!(response_variant_name("200"), "S200");
+        assert_eq!(response_variant_name("2xx"), "S2XX");
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use derive_more::{Display, Error};
+use serde_json::{Map, Value};
+
+use crate::spec::split_ref;
+
+/// Recursively resolves `$ref`s with a non-empty `source` (a sibling file or an http(s)
+/// URL) against a root spec document, so a spec can be split across multiple files. Each
+/// referenced component is loaded, merged into the root document's `components` under a
+/// name namespaced by its source, and the original `$ref` rewritten to point at the local,
+/// merged-in copy. Everything downstream (`Analyzer`, `DefaultRenamer`, ...) only ever sees
+/// local, in-memory `$ref`s and doesn't need to know bundling happened.
+pub struct Bundler {
+    origin: Origin,
+    cache: HashMap<String, Value>,
+}
+
+#[derive(Clone, Debug)]
+enum Origin {
+    File(PathBuf),
+    Url(String),
+}
+
+impl Origin {
+    fn resolve(&self, source: &str) -> Origin {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            return Origin::Url(source.to_owned());
+        }
+        match self {
+            Origin::File(dir) => Origin::File(dir.join(source)),
+            Origin::Url(base) => {
+                let dir = &base[..base.rfind('/').map(|i| i + 1).unwrap_or(base.len())];
+                Origin::Url(format!("{}{}", dir, source))
+            }
+        }
+    }
+
+    fn key(&self) -> String {
+        match self {
+            Origin::File(path) => path.to_string_lossy().into_owned(),
+            Origin::Url(url) => url.clone(),
+        }
+    }
+
+    fn dir(&self) -> Origin {
+        match self {
+            Origin::File(path) => Origin::File(
+                path.parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|| PathBuf::from(".")),
+            ),
+            Origin::Url(url) => {
+                let dir = &url[..url.rfind('/').map(|i| i + 1).unwrap_or(url.len())];
+                Origin::Url(dir.to_owned())
+            }
+        }
+    }
+
+    fn load(&self) -> Result<Value, BundlerError> {
+        let content = match self {
+            Origin::File(path) => fs::read_to_string(path)
+                .map_err(|e| BundlerError::Io(path.to_string_lossy().into_owned(), e))?,
+            Origin::Url(url) => ureq::get(url)
+                .call()
+                .map_err(|e| BundlerError::Http(url.clone(), Box::new(e)))?
+                .into_string()
+                .map_err(|e| BundlerError::Io(url.clone(), e))?,
+        };
+        match serde_json::from_str(&content) {
+            Ok(value) => Ok(value),
+            Err(json_err) => serde_yaml::from_str(&content)
+                .map_err(|yaml_err| BundlerError::Parse(self.key(), json_err, yaml_err)),
+        }
+    }
+}
+
+type ComponentsByKind = HashMap<String, Map<String, Value>>;
+
+#[derive(Debug, Display, Error)]
+pub enum BundlerError {
+    #[display(fmt = "could not read referenced spec `{}`: {}", _0, _1)]
+    Io(#[error(not(source))] String, std::io::Error),
+
+    #[display(fmt = "could not fetch referenced spec `{}`: {}", _0, _1)]
+    Http(#[error(not(source))] String, Box<ureq::Error>),
+
+    #[display(fmt = "could not parse referenced spec `{}` as JSON ({}) or YAML ({})", _0, _1, _2)]
+    Parse(
+        #[error(not(source))] String,
+        serde_json::Error,
+        #[error(not(source))] serde_yaml::Error,
+    ),
+
+    #[display(fmt = "`{}` has no component `{}/{}`", _0, _1, _2)]
+    Unresolvable(
+        #[error(not(source))] String,
+        #[error(not(source))] String,
+        #[error(not(source))] String,
+    ),
+}
+
+impl Bundler {
+    /// Creates a bundler resolving relative sources against `base_dir` (typically the
+    /// directory containing the entry spec file).
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Bundler {
+            origin: Origin::File(base_dir.into()),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Bundles `root` into a self-contained document: every external `$ref` is resolved,
+    /// its target merged into `root`'s `components`, and the `$ref` rewritten to the local
+    /// pointer of the merged-in copy.
+    pub fn bundle(&mut self, mut root: Value) -> Result<Value, BundlerError> {
+        let mut extra = ComponentsByKind::new();
+        self.inline_refs(self.origin.clone(), &mut root, &mut extra)?;
+
+        if !extra.is_empty() {
+            let components = root
+                .as_object_mut()
+                .expect("spec root should be an object")
+                .entry("components")
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .expect("components should be an object");
+            for (kind, named) in extra {
+                components
+                    .entry(kind)
+                    .or_insert_with(|| Value::Object(Map::new()))
+                    .as_object_mut()
+                    .expect("component bucket should be an object")
+                    .extend(named);
+            }
+        }
+
+        Ok(root)
+    }
+
+    fn inline_refs(
+        &mut self,
+        here: Origin,
+        value: &mut Value,
+        extra: &mut ComponentsByKind,
+    ) -> Result<(), BundlerError> {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(ref_path)) = map.get("$ref") {
+                    if let Some((source, kind, name)) = split_ref(ref_path) {
+                        if !source.is_empty() {
+                            let local_name = self.bundle_external(&here, &source, &kind, &name, extra)?;
+                            map.insert(
+                                "$ref".to_owned(),
+                                Value::String(format!("#/components/{}/{}", kind, local_name)),
+                            );
+                        }
+                    }
+                }
+                for v in map.values_mut() {
+                    self.inline_refs(here.clone(), v, extra)?;
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.inline_refs(here.clone(), item, extra)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Loads the document `source` (relative to `here`), pulls out its `type/name`
+    /// component, merges it into `extra` under a name namespaced by `source`, and returns
+    /// that local name. Refs inside the loaded component are bundled recursively, relative
+    /// to the loaded document's own location.
+    fn bundle_external(
+        &mut self,
+        here: &Origin,
+        source: &str,
+        kind: &str,
+        name: &str,
+        extra: &mut ComponentsByKind,
+    ) -> Result<String, BundlerError> {
+        let document_origin = here.resolve(source);
+        let local_name = format!("{}{}", namespace_for(&document_origin.key()), name);
+
+        if extra.get(kind).and_then(|n| n.get(&local_name)).is_some() {
+            return Ok(local_name);
+        }
+
+        let document = match self.cache.get(&document_origin.key()) {
+            Some(document) => document.clone(),
+            None => {
+                let document = document_origin.load()?;
+                self.cache.insert(document_origin.key(), document.clone());
+                document
+            }
+        };
+
+        let mut component = document
+            .pointer(&format!("/components/{}/{}", kind, name))
+            .cloned()
+            .ok_or_else(|| {
+                BundlerError::Unresolvable(document_origin.key(), kind.to_owned(), name.to_owned())
+            })?;
+
+        // Marks `local_name` as seen before recursing into its own body, not after: a
+        // self-referential or mutually-recursive external `$ref` would otherwise re-enter
+        // `bundle_external` for the same `(kind, name)` before it's ever marked seen and
+        // recurse forever.
+        extra
+            .entry(kind.to_owned())
+            .or_default()
+            .insert(local_name.clone(), Value::Null);
+
+        self.inline_refs(document_origin.dir(), &mut component, extra)?;
+
+        extra
+            .entry(kind.to_owned())
+            .or_default()
+            .insert(local_name.clone(), component);
+
+        Ok(local_name)
+    }
+}
+
+/// Turns a document's fully resolved origin `key` (a full file path or URL, not just the
+/// `$ref`'s relative `source`) into an identifier-safe prefix so components pulled in from
+/// different files can't collide, while still letting `DefaultRenamer`'s
+/// `/components/<kind>/<name>` regexes match unchanged. Namespacing on the basename stem alone
+/// would collide two same-named files in different directories (`a/common.yaml` and
+/// `b/common.yaml` both becoming `common_`); a hash of the full resolved key is appended to keep
+/// the prefix both readable and unique.
+fn namespace_for(key: &str) -> String {
+    let stem = key
+        .rsplit('/')
+        .next()
+        .unwrap_or(key)
+        .split('.')
+        .next()
+        .unwrap_or(key);
+    let safe: String = stem
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    format!("{}_{:x}_", safe, hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespaces_same_basename_in_different_directories_differently() {
+        let a = namespace_for("/specs/a/common.yaml");
+        let b = namespace_for("/specs/b/common.yaml");
+        assert_ne!(a, b, "same-named files in different directories must not collide");
+        assert!(a.starts_with("common_"));
+        assert!(b.starts_with("common_"));
+    }
+
+    #[test]
+    fn namespace_for_is_deterministic() {
+        assert_eq!(
+            namespace_for("/specs/common.yaml"),
+            namespace_for("/specs/common.yaml")
+        );
+    }
+
+    #[test]
+    fn namespace_for_is_identifier_safe() {
+        let namespace = namespace_for("https://example.com/common-v2.yaml");
+        assert!(namespace.chars().all(|c| c.is_alphanumeric() || c == '_'));
+    }
+}
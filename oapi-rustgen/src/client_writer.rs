@@ -3,18 +3,40 @@ use derive_more::{Display, Error};
 use genco::{prelude::rust::Tokens, quote, tokens::quoted};
 use http::Method;
 
-use crate::{analyzer::AnalysisResult, spec::ParameterLocation, OperationDef, SegmentOrParameter};
+use crate::{
+    analyzer::{
+        is_dynamic_response_status, response_status_range, response_variant_name, AnalysisResult,
+        SecuritySchemeKind,
+    },
+    spec::ParameterLocation,
+    CollectionFormat, MediaTypeKind, OperationDef, ParameterDef, SegmentOrParameter,
+};
 
 #[derive(Debug, Display, Error)]
 pub enum ClientWriterError {}
 
+/// Which HTTP crate the generated `Client` implementation is built on. Mirrors
+/// [`crate::ServerBackend`] -- same `Client` trait and model types either way, just a
+/// different concrete struct implementing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClientBackend {
+    #[default]
+    Awc,
+    Reqwest,
+}
+
 pub struct ClientWriter<'a> {
     analysis: &'a AnalysisResult,
+    backend: ClientBackend,
 }
 
 impl<'a> ClientWriter<'a> {
     pub fn new(analysis: &'a AnalysisResult) -> Self {
-        ClientWriter { analysis }
+        Self::with_backend(analysis, ClientBackend::Awc)
+    }
+
+    pub fn with_backend(analysis: &'a AnalysisResult, backend: ClientBackend) -> Self {
+        ClientWriter { analysis, backend }
     }
 
     pub fn write(&self) -> Result<Tokens, ClientWriterError> {
@@ -52,19 +74,31 @@ impl<'a> ClientWriter<'a> {
         tokens.append(&unexpected_response_error_def);
         tokens.line();
 
-        tokens.append(&self.write_awc_client());
+        tokens.append(&match self.backend {
+            ClientBackend::Awc => self.write_awc_client(),
+            ClientBackend::Reqwest => self.write_reqwest_client(),
+        });
 
         Ok(tokens)
     }
 
     // This function should probably be somewhere else
     pub fn write_operation_function_signature(o: &OperationDef) -> Tokens {
+        let return_type = match o.streaming_response() {
+            Some((_, item_type)) => quote! {
+                std::pin::Pin<Box<dyn futures::Stream<Item = Result<$(item_type.to_owned()), Self::Error>>>>
+            },
+            None => quote! { $(o.response()) },
+        };
         quote! {
             async fn $(o.name())(
                 &self,
                 $(for (_, ty) in o.path_params() join (, ) => $(ty.name()): $(ty.schema_type()))$(if o.path_params().count() > 0 { ,  })
+                $(for p in o.query_params() join (, ) => $(p.name()): $(p.argument_type()))$(if o.query_params().count() > 0 { ,  })
+                $(for p in o.header_params() join (, ) => $(p.name()): $(p.argument_type()))$(if o.header_params().count() > 0 { ,  })
+                $(for p in o.cookie_params() join (, ) => $(p.name()): $(p.argument_type()))$(if o.cookie_params().count() > 0 { ,  })
                 $(if let Some(b) = o.request_body() { body: $(b),  })
-            ) -> Result<$(o.response()), Self::Error>
+            ) -> Result<$(return_type), Self::Error>
         }
     }
 
@@ -75,6 +109,10 @@ impl<'a> ClientWriter<'a> {
             pub struct AwcClient {
                 c: awc::Client,
                 base_url: String,
+                /// Credentials to attach to requests, keyed by `securitySchemes` name. Set via
+                /// `with_credential`; an operation requiring a scheme that has none configured
+                /// is simply sent without it (and the server will reject it with a `401`).
+                credentials: std::collections::HashMap<String, String>,
             }
 
             impl AwcClient {
@@ -82,8 +120,16 @@ impl<'a> ClientWriter<'a> {
                     Self {
                         c: c.clone(),
                         base_url: base_url.to_owned(),
+                        credentials: std::collections::HashMap::new(),
                     }
                 }
+
+                /// Registers the credential to send for a given `securitySchemes` name (e.g. a
+                /// bearer token, a pre-encoded `user:password` basic-auth value, or an apiKey).
+                pub fn with_credential(mut self, scheme: &str, value: impl Into<String>) -> Self {
+                    self.credentials.insert(scheme.to_owned(), value.into());
+                    self
+                }
             }
         };
         tokens.append(&awc_client_def);
@@ -94,23 +140,7 @@ impl<'a> ClientWriter<'a> {
             impl Client for AwcClient {
                 type Error = Box<dyn std::error::Error>;
                 
-                $(for o in &self.analysis.operations() =>
-                    $(Self::write_operation_function_signature(o)) {
-                        let method = $(self.write_awc_method(o));
-                        let url = $(self.write_awc_path(o));
-                        let $(if o.has_any_response_body() { mut }) res = self.c.request(method.clone(), url.clone()).$(if o.request_body().is_some() { send_json(&body) } else { send() }).await?;
-                        match res.status().as_u16() {
-                            $(for (status_code, r) in o.responses() join (, ) => $(self.write_awc_response_handler(o, status_code, r))),
-                            $(if !o.has_default_response() {
-                                _ => Err(Box::new(UnexpectedResponse {
-                                    method: method.to_string(),
-                                    url: url.to_owned(),
-                                    status_code: res.status().as_u16()
-                                })),
-                            })
-                        }
-                    }
-                )
+                $(for o in &self.analysis.operations() => $(self.write_awc_operation(o)))
             }
         };
         tokens.append(&awc_client_impl);
@@ -119,6 +149,269 @@ impl<'a> ClientWriter<'a> {
         tokens
     }
 
+    fn write_awc_operation(&self, o: &OperationDef) -> Tokens {
+        if let Some((_, item_type)) = o.streaming_response() {
+            return self.write_awc_streaming_operation(o, item_type);
+        }
+
+        quote! {
+            $(Self::write_operation_function_signature(o)) {
+                let method = $(self.write_awc_method(o));
+                let url = $(self.write_request_path(o));
+                let mut req = self.c.request(method.clone(), url.clone());
+                $(self.write_awc_query_and_headers(o))
+                let $(if o.has_any_response_body() { mut }) res = req.$(self.write_awc_send_expr(o)).await?;
+                match res.status().as_u16() {
+                    $(for (status_code, r) in o.responses() join (, ) => $(self.write_awc_response_handler(o, status_code, r))),
+                    $(if !o.has_default_response() {
+                        _ => Err(Box::new(UnexpectedResponse {
+                            method: method.to_string(),
+                            url: url.to_owned(),
+                            status_code: res.status().as_u16()
+                        })),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Reads an SSE (`text/event-stream`) or ndjson body as `impl Stream`, splitting on blank-line
+    /// `data:`-framed events and JSON-decoding each one individually. ndjson bodies are read with
+    /// the same SSE framing (which the generated servers emit for both); bare newline-delimited
+    /// records without a `data:` prefix yield no items.
+    fn write_awc_streaming_operation(&self, o: &OperationDef, item_type: &str) -> Tokens {
+        quote! {
+            $(Self::write_operation_function_signature(o)) {
+                let method = $(self.write_awc_method(o));
+                let url = $(self.write_request_path(o));
+                let mut req = self.c.request(method.clone(), url.clone());
+                $(self.write_awc_query_and_headers(o))
+                let mut res = req.send().await?;
+
+                let stream = futures::stream::unfold((res.take_payload(), bytes::BytesMut::new()), |(mut body, mut buf)| async move {
+                    loop {
+                        if let Some(pos) = buf.windows(2).position(|w| w == b"\n\n") {
+                            let frame = buf.split_to(pos + 2);
+                            let data = String::from_utf8_lossy(&frame)
+                                .lines()
+                                .filter_map(|line| line.strip_prefix("data:"))
+                                .map(|line| line.trim())
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            if data.is_empty() {
+                                continue;
+                            }
+                            let item: Result<$(item_type.to_owned()), Box<dyn std::error::Error>> =
+                                serde_json::from_str(&data).map_err(|e| Box::new(e) as Box<dyn std::error::Error>);
+                            return Some((item, (body, buf)));
+                        }
+
+                        match futures::StreamExt::next(&mut body).await {
+                            Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                            Some(Err(e)) => return Some((Err(Box::new(e) as Box<dyn std::error::Error>), (body, buf))),
+                            None => return None,
+                        }
+                    }
+                });
+
+                Ok(Box::pin(stream))
+            }
+        }
+    }
+
+    /// Attaches whichever of `o`'s accepted security schemes have a credential configured on
+    /// the client. If several are configured, all get attached; the server accepts any one.
+    fn write_awc_credential_attach(&self, o: &OperationDef) -> Tokens {
+        let schemes = self.analysis.security_schemes();
+        let attachments: Vec<Tokens> = o
+            .security()
+            .iter()
+            .filter_map(|name| schemes.get(name))
+            .map(|scheme| {
+                let scheme_name = quoted(scheme.name().to_owned());
+                match scheme.kind() {
+                    SecuritySchemeKind::Bearer => quote! {
+                        if let Some(token) = self.credentials.get($(&scheme_name)) {
+                            req = req.insert_header(("Authorization", format!("Bearer {}", token)));
+                        }
+                    },
+                    SecuritySchemeKind::Basic => quote! {
+                        if let Some(value) = self.credentials.get($(&scheme_name)) {
+                            req = req.insert_header(("Authorization", format!("Basic {}", value)));
+                        }
+                    },
+                    SecuritySchemeKind::ApiKey { location: ParameterLocation::Query, name } => {
+                        let key_name = quoted(name.to_owned());
+                        quote! {
+                            if let Some(value) = self.credentials.get($(&scheme_name)) {
+                                req = req.query(&[($(key_name), value.clone())]).expect("should serialize api key");
+                            }
+                        }
+                    }
+                    SecuritySchemeKind::ApiKey { location: ParameterLocation::Cookie, name } => {
+                        let key_name = quoted(name.to_owned());
+                        quote! {
+                            if let Some(value) = self.credentials.get($(&scheme_name)) {
+                                req = req.insert_header(("Cookie", format!("{}={}", $(key_name), value)));
+                            }
+                        }
+                    }
+                    SecuritySchemeKind::ApiKey { name, .. } => {
+                        let key_name = quoted(name.to_owned());
+                        quote! {
+                            if let Some(value) = self.credentials.get($(&scheme_name)) {
+                                req = req.insert_header(($(key_name), value.clone()));
+                            }
+                        }
+                    }
+                }
+            })
+            .collect();
+        quote! { $(for a in attachments => $(a)) }
+    }
+
+    /// Picks the `awc` request-builder call that sends `o`'s request body per its declared
+    /// media type: `send_json`/`send_form` set their own `Content-Type`, while raw bytes/plain
+    /// text bodies rely on [`write_awc_request_content_type`](Self::write_awc_request_content_type)
+    /// to have set one already.
+    fn write_awc_send_expr(&self, o: &OperationDef) -> Tokens {
+        match o.request_body_media_type() {
+            None => quote! { send() },
+            Some(content_type) => match self.analysis.media_types().kind_for(content_type) {
+                MediaTypeKind::Json => quote! { send_json(&body) },
+                MediaTypeKind::UrlEncoded => quote! { send_form(&body) },
+                MediaTypeKind::PlainText | MediaTypeKind::Bytes => quote! { send_body(body) },
+            },
+        }
+    }
+
+    /// `send_body` doesn't set a `Content-Type` the way `send_json`/`send_form` do, so raw
+    /// bytes/plain-text request bodies need it set explicitly beforehand.
+    fn write_awc_request_content_type(&self, o: &OperationDef) -> Tokens {
+        let Some(content_type) = o.request_body_media_type() else {
+            return Tokens::new();
+        };
+        match self.analysis.media_types().kind_for(content_type) {
+            MediaTypeKind::PlainText | MediaTypeKind::Bytes => {
+                let content_type_quoted = quoted(content_type.to_owned());
+                quote! { req = req.content_type($(content_type_quoted)); }
+            }
+            MediaTypeKind::Json | MediaTypeKind::UrlEncoded => Tokens::new(),
+        }
+    }
+
+    /// Decodes a response body per its declared media type; `res.json()` for JSON, otherwise
+    /// reading the raw body and decoding it according to [`MediaTypeKind`].
+    fn write_awc_decode_body(&self, content_type: &str) -> Tokens {
+        match self.analysis.media_types().kind_for(content_type) {
+            MediaTypeKind::Json => quote! { res.json().await? },
+            MediaTypeKind::UrlEncoded => quote! { serde_urlencoded::from_bytes(&res.body().await?)? },
+            MediaTypeKind::PlainText => quote! { String::from_utf8(res.body().await?.to_vec())? },
+            MediaTypeKind::Bytes => quote! { res.body().await?.to_vec() },
+        }
+    }
+
+    fn write_awc_query_and_headers(&self, o: &OperationDef) -> Tokens {
+        let has_query = o.query_params().count() > 0;
+        quote! {
+            $(self.write_awc_credential_attach(o))
+            $(self.write_awc_request_content_type(o))
+            $(if has_query {
+                let mut query_pairs: Vec<(String, String)> = vec![];
+                $(for p in o.query_params() => $(self.write_awc_query_push(p)))
+                if !query_pairs.is_empty() {
+                    req = req.query(&query_pairs).expect("should serialize query parameters");
+                }
+            })
+            $(for p in o.header_params() => $(self.write_awc_header_insert(p)))
+            $(if o.cookie_params().count() > 0 {
+                let mut cookie_pairs: Vec<String> = vec![];
+                $(for p in o.cookie_params() => $(self.write_awc_cookie_push(p)))
+                if !cookie_pairs.is_empty() {
+                    req = req.insert_header(("Cookie", cookie_pairs.join("; ")));
+                }
+            })
+        }
+    }
+
+    fn write_awc_cookie_push(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        if p.required() {
+            quote! {
+                cookie_pairs.push(format!("{}={}", $(&name), $(p.name())));
+            }
+        } else {
+            quote! {
+                if let Some(value) = &$(p.name()) {
+                    cookie_pairs.push(format!("{}={}", $(&name), value));
+                }
+            }
+        }
+    }
+
+    fn write_awc_query_push(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        match p.collection_format() {
+            Some(CollectionFormat::Multi) if p.required() => quote! {
+                for v in &$(p.name()) {
+                    query_pairs.push(($(&name), v.to_string()));
+                }
+            },
+            Some(CollectionFormat::Multi) => quote! {
+                if let Some(values) = &$(p.name()) {
+                    for v in values {
+                        query_pairs.push(($(&name), v.to_string()));
+                    }
+                }
+            },
+            Some(format) => {
+                let separator = quoted(self.collection_format_separator(format));
+                if p.required() {
+                    quote! {
+                        query_pairs.push(($(&name), $(p.name()).iter().map(|v| v.to_string()).collect::<Vec<_>>().join($(separator))));
+                    }
+                } else {
+                    quote! {
+                        if let Some(values) = &$(p.name()) {
+                            query_pairs.push(($(&name), values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join($(separator))));
+                        }
+                    }
+                }
+            }
+            None if p.required() => quote! {
+                query_pairs.push(($(&name), $(p.name()).to_string()));
+            },
+            None => quote! {
+                if let Some(value) = &$(p.name()) {
+                    query_pairs.push(($(&name), value.to_string()));
+                }
+            },
+        }
+    }
+
+    fn write_awc_header_insert(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        if p.required() {
+            quote! { req = req.insert_header(($(&name), $(p.name()).to_string())); }
+        } else {
+            quote! {
+                if let Some(value) = &$(p.name()) {
+                    req = req.insert_header(($(&name), value.to_string()));
+                }
+            }
+        }
+    }
+
+    fn collection_format_separator(&self, format: CollectionFormat) -> &'static str {
+        match format {
+            CollectionFormat::Multi => unreachable!("handled separately"),
+            CollectionFormat::Csv => ",",
+            CollectionFormat::Ssv => " ",
+            CollectionFormat::Pipes => "|",
+            CollectionFormat::Tsv => "\t",
+        }
+    }
+
     fn write_awc_method(&self, o: &OperationDef) -> Tokens {
         match *o.method() {
             Method::CONNECT => quote! { awc::http::Method::CONNECT },
@@ -134,7 +427,7 @@ impl<'a> ClientWriter<'a> {
         }
     }
 
-    fn write_awc_path(&self, o: &OperationDef) -> Tokens {
+    fn write_request_path(&self, o: &OperationDef) -> Tokens {
         let format_string = o.path().iter().fold("{}".to_owned(), |memo, v| match v {
             SegmentOrParameter::Segment(s) => format!("{}/{}", memo, s),
             SegmentOrParameter::Parameter(_) => format!("{}/{{}}", memo),
@@ -155,32 +448,375 @@ impl<'a> ClientWriter<'a> {
         status_code: &str,
         response: &Option<String>,
     ) -> Tokens {
-        let match_value: Tokens = match status_code.parse::<u16>() {
-            Ok(status_code) => quote! { $(status_code) },
-            Err(_) if status_code == "default" => quote! { _ },
-            _ => panic!("could not parse status code {}", &status_code),
+        let is_dynamic = is_dynamic_response_status(status_code);
+        let match_value: Tokens = if let Some((lo, hi)) = response_status_range(status_code) {
+            quote! { s @ $(lo)..=$(hi) }
+        } else if status_code.eq_ignore_ascii_case("default") {
+            quote! { s }
+        } else {
+            match status_code.parse::<u16>() {
+                Ok(status_code) => quote! { $(status_code) },
+                Err(_) => panic!("could not parse status code {}", &status_code),
+            }
         };
+        let variant_name = response_variant_name(status_code);
+        let content_type = operation.response_media_type(status_code).unwrap_or("application/json");
         let match_arm: Tokens = if operation.responses().len() == 1 {
             if operation.response() == "()" {
                 quote! { Ok($(operation.response())) }
             } else {
+                let decode = self.write_awc_decode_body(content_type);
                 quote! {
                     {
-                        let body: $(operation.response()) = res.json().await?;
+                        let body: $(operation.response()) = $(decode);
                         Ok(body)
                     }
                 }
             }
         } else {
             match response {
-                Some(schema_type) => quote! {
-                    {
-                        let body: $(schema_type) = res.json().await?;
-                        Ok($(operation.response())::S$(status_code)(body))
+                Some(schema_type) => {
+                    let decode = self.write_awc_decode_body(content_type);
+                    quote! {
+                        {
+                            let body: $(schema_type) = $(decode);
+                            Ok($(operation.response())::$(variant_name)($(if is_dynamic { s, }) body))
+                        }
                     }
+                }
+                None => quote! {
+                    Ok($(operation.response())::$(variant_name)$(if is_dynamic { (s) }))
                 },
+            }
+        };
+
+        quote! { $(match_value) => $(match_arm) }
+    }
+
+    fn write_reqwest_client(&self) -> Tokens {
+        let mut tokens = Tokens::new();
+
+        let error_def: Tokens = quote! {
+            /// Distinguishes the ways a `ReqwestClient` call can fail: the request never made
+            /// it (or the transport itself errored), the response body didn't decode, or the
+            /// server returned a status the spec doesn't declare a `default` for.
+            #[derive(Debug)]
+            pub enum ClientError {
+                Transport(reqwest::Error),
+                Deserialization(String),
+                UnexpectedStatus(UnexpectedResponse),
+            }
+
+            impl std::fmt::Display for ClientError {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match self {
+                        ClientError::Transport(e) => write!(f, "transport error: {}", e),
+                        ClientError::Deserialization(e) => write!(f, "deserialization error: {}", e),
+                        ClientError::UnexpectedStatus(e) => write!(f, "{}", e),
+                    }
+                }
+            }
+
+            impl std::error::Error for ClientError {}
+
+            impl From<reqwest::Error> for ClientError {
+                fn from(e: reqwest::Error) -> Self {
+                    ClientError::Transport(e)
+                }
+            }
+        };
+        tokens.append(&error_def);
+        tokens.line();
+
+        let reqwest_client_def: Tokens = quote! {
+            pub struct ReqwestClient {
+                c: reqwest::Client,
+                base_url: String,
+                /// Credentials to attach to requests, keyed by `securitySchemes` name. Set via
+                /// `with_credential`; an operation requiring a scheme that has none configured
+                /// is simply sent without it (and the server will reject it with a `401`).
+                credentials: std::collections::HashMap<String, String>,
+            }
+
+            impl ReqwestClient {
+                pub fn new(c: &reqwest::Client, base_url: &str) -> Self {
+                    Self {
+                        c: c.clone(),
+                        base_url: base_url.to_owned(),
+                        credentials: std::collections::HashMap::new(),
+                    }
+                }
+
+                /// Registers the credential to send for a given `securitySchemes` name (e.g. a
+                /// bearer token, a pre-encoded `user:password` basic-auth value, or an apiKey).
+                pub fn with_credential(mut self, scheme: &str, value: impl Into<String>) -> Self {
+                    self.credentials.insert(scheme.to_owned(), value.into());
+                    self
+                }
+            }
+        };
+        tokens.append(&reqwest_client_def);
+        tokens.line();
+
+        let reqwest_client_impl: Tokens = quote! {
+            #[async_trait::async_trait(?Send)]
+            impl Client for ReqwestClient {
+                type Error = ClientError;
+
+                $(for o in &self.analysis.operations() => $(self.write_reqwest_operation(o)))
+            }
+        };
+        tokens.append(&reqwest_client_impl);
+        tokens.line();
+
+        tokens
+    }
+
+    fn write_reqwest_operation(&self, o: &OperationDef) -> Tokens {
+        if let Some((_, item_type)) = o.streaming_response() {
+            return self.write_reqwest_streaming_operation(o, item_type);
+        }
+
+        quote! {
+            $(Self::write_operation_function_signature(o)) {
+                let method = $(self.write_reqwest_method(o));
+                let url = $(self.write_request_path(o));
+                let mut req = self.c.request(method.clone(), url.as_str());
+                $(self.write_reqwest_query_and_headers(o))
+                $(self.write_reqwest_body_attach(o))
+                let res = req.send().await?;
+                let status_code = res.status().as_u16();
+                match status_code {
+                    $(for (status_code, r) in o.responses() join (, ) => $(self.write_reqwest_response_handler(o, status_code, r))),
+                    $(if !o.has_default_response() {
+                        _ => Err(ClientError::UnexpectedStatus(UnexpectedResponse {
+                            method: method.to_string(),
+                            url: url.to_owned(),
+                            status_code,
+                        })),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Mirrors `ClientWriter::write_awc_streaming_operation`, but reads the body via
+    /// `reqwest`'s `bytes_stream` instead of awc's `take_payload`.
+    fn write_reqwest_streaming_operation(&self, o: &OperationDef, item_type: &str) -> Tokens {
+        quote! {
+            $(Self::write_operation_function_signature(o)) {
+                let method = $(self.write_reqwest_method(o));
+                let url = $(self.write_request_path(o));
+                let mut req = self.c.request(method.clone(), url.as_str());
+                $(self.write_reqwest_query_and_headers(o))
+                let res = req.send().await?;
+
+                let stream = futures::stream::unfold((res.bytes_stream(), bytes::BytesMut::new()), |(mut body, mut buf)| async move {
+                    loop {
+                        if let Some(pos) = buf.windows(2).position(|w| w == b"\n\n") {
+                            let frame = buf.split_to(pos + 2);
+                            let data = String::from_utf8_lossy(&frame)
+                                .lines()
+                                .filter_map(|line| line.strip_prefix("data:"))
+                                .map(|line| line.trim())
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            if data.is_empty() {
+                                continue;
+                            }
+                            let item: Result<$(item_type.to_owned()), ClientError> =
+                                serde_json::from_str(&data).map_err(|e| ClientError::Deserialization(e.to_string()));
+                            return Some((item, (body, buf)));
+                        }
+
+                        match futures::StreamExt::next(&mut body).await {
+                            Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                            Some(Err(e)) => return Some((Err(ClientError::from(e)), (body, buf))),
+                            None => return None,
+                        }
+                    }
+                });
+
+                Ok(Box::pin(stream))
+            }
+        }
+    }
+
+    /// Mirrors `ClientWriter::write_awc_credential_attach`, but `reqwest`'s `RequestBuilder`
+    /// takes a plain query value instead of awc's fallible `query`.
+    fn write_reqwest_credential_attach(&self, o: &OperationDef) -> Tokens {
+        let schemes = self.analysis.security_schemes();
+        let attachments: Vec<Tokens> = o
+            .security()
+            .iter()
+            .filter_map(|name| schemes.get(name))
+            .map(|scheme| {
+                let scheme_name = quoted(scheme.name().to_owned());
+                match scheme.kind() {
+                    SecuritySchemeKind::Bearer => quote! {
+                        if let Some(token) = self.credentials.get($(&scheme_name)) {
+                            req = req.header("Authorization", format!("Bearer {}", token));
+                        }
+                    },
+                    SecuritySchemeKind::Basic => quote! {
+                        if let Some(value) = self.credentials.get($(&scheme_name)) {
+                            req = req.header("Authorization", format!("Basic {}", value));
+                        }
+                    },
+                    SecuritySchemeKind::ApiKey { location: ParameterLocation::Query, name } => {
+                        let key_name = quoted(name.to_owned());
+                        quote! {
+                            if let Some(value) = self.credentials.get($(&scheme_name)) {
+                                req = req.query(&[($(key_name), value.clone())]);
+                            }
+                        }
+                    }
+                    SecuritySchemeKind::ApiKey { location: ParameterLocation::Cookie, name } => {
+                        let key_name = quoted(name.to_owned());
+                        quote! {
+                            if let Some(value) = self.credentials.get($(&scheme_name)) {
+                                req = req.header("Cookie", format!("{}={}", $(key_name), value));
+                            }
+                        }
+                    }
+                    SecuritySchemeKind::ApiKey { name, .. } => {
+                        let key_name = quoted(name.to_owned());
+                        quote! {
+                            if let Some(value) = self.credentials.get($(&scheme_name)) {
+                                req = req.header($(key_name), value.clone());
+                            }
+                        }
+                    }
+                }
+            })
+            .collect();
+        quote! { $(for a in attachments => $(a)) }
+    }
+
+    /// Picks the `reqwest` request-builder call that sends `o`'s request body per its declared
+    /// media type: `json`/`form` set their own `Content-Type`, raw bytes/plain text bodies set
+    /// one explicitly since `body` doesn't.
+    fn write_reqwest_body_attach(&self, o: &OperationDef) -> Tokens {
+        match o.request_body_media_type() {
+            None => Tokens::new(),
+            Some(content_type) => match self.analysis.media_types().kind_for(content_type) {
+                MediaTypeKind::Json => quote! { req = req.json(&body); },
+                MediaTypeKind::UrlEncoded => quote! { req = req.form(&body); },
+                MediaTypeKind::PlainText | MediaTypeKind::Bytes => {
+                    let content_type_quoted = quoted(content_type.to_owned());
+                    quote! { req = req.header(reqwest::header::CONTENT_TYPE, $(content_type_quoted)).body(body); }
+                }
+            },
+        }
+    }
+
+    /// Decodes a response body per its declared media type. `serde_urlencoded` errors don't
+    /// convert to `ClientError` via `?` the way `reqwest::Error` does, so they're mapped by hand.
+    fn write_reqwest_decode_body(&self, content_type: &str) -> Tokens {
+        match self.analysis.media_types().kind_for(content_type) {
+            MediaTypeKind::Json => quote! { res.json().await? },
+            MediaTypeKind::UrlEncoded => quote! {
+                serde_urlencoded::from_bytes(&res.bytes().await?).map_err(|e| ClientError::Deserialization(e.to_string()))?
+            },
+            MediaTypeKind::PlainText => quote! { res.text().await? },
+            MediaTypeKind::Bytes => quote! { res.bytes().await?.to_vec() },
+        }
+    }
+
+    fn write_reqwest_query_and_headers(&self, o: &OperationDef) -> Tokens {
+        let has_query = o.query_params().count() > 0;
+        quote! {
+            $(self.write_reqwest_credential_attach(o))
+            $(if has_query {
+                let mut query_pairs: Vec<(String, String)> = vec![];
+                $(for p in o.query_params() => $(self.write_awc_query_push(p)))
+                if !query_pairs.is_empty() {
+                    req = req.query(&query_pairs);
+                }
+            })
+            $(for p in o.header_params() => $(self.write_reqwest_header_insert(p)))
+            $(if o.cookie_params().count() > 0 {
+                let mut cookie_pairs: Vec<String> = vec![];
+                $(for p in o.cookie_params() => $(self.write_awc_cookie_push(p)))
+                if !cookie_pairs.is_empty() {
+                    req = req.header("Cookie", cookie_pairs.join("; "));
+                }
+            })
+        }
+    }
+
+    fn write_reqwest_header_insert(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        if p.required() {
+            quote! { req = req.header($(&name), $(p.name()).to_string()); }
+        } else {
+            quote! {
+                if let Some(value) = &$(p.name()) {
+                    req = req.header($(&name), value.to_string());
+                }
+            }
+        }
+    }
+
+    fn write_reqwest_method(&self, o: &OperationDef) -> Tokens {
+        match *o.method() {
+            Method::CONNECT => quote! { reqwest::Method::CONNECT },
+            Method::DELETE => quote! { reqwest::Method::DELETE },
+            Method::GET => quote! { reqwest::Method::GET },
+            Method::HEAD => quote! { reqwest::Method::HEAD },
+            Method::OPTIONS => quote! { reqwest::Method::OPTIONS },
+            Method::PATCH => quote! { reqwest::Method::PATCH },
+            Method::POST => quote! { reqwest::Method::POST },
+            Method::PUT => quote! { reqwest::Method::PUT },
+            Method::TRACE => quote! { reqwest::Method::TRACE },
+            _ => panic!("unknown method `{:?}` for reqwest client", o.method()),
+        }
+    }
+
+    fn write_reqwest_response_handler(
+        &self,
+        operation: &OperationDef,
+        status_code: &str,
+        response: &Option<String>,
+    ) -> Tokens {
+        let is_dynamic = is_dynamic_response_status(status_code);
+        let match_value: Tokens = if let Some((lo, hi)) = response_status_range(status_code) {
+            quote! { s @ $(lo)..=$(hi) }
+        } else if status_code.eq_ignore_ascii_case("default") {
+            quote! { s }
+        } else {
+            match status_code.parse::<u16>() {
+                Ok(status_code) => quote! { $(status_code) },
+                Err(_) => panic!("could not parse status code {}", &status_code),
+            }
+        };
+        let variant_name = response_variant_name(status_code);
+        let content_type = operation.response_media_type(status_code).unwrap_or("application/json");
+        let match_arm: Tokens = if operation.responses().len() == 1 {
+            if operation.response() == "()" {
+                quote! { Ok($(operation.response())) }
+            } else {
+                let decode = self.write_reqwest_decode_body(content_type);
+                quote! {
+                    {
+                        let body: $(operation.response()) = $(decode);
+                        Ok(body)
+                    }
+                }
+            }
+        } else {
+            match response {
+                Some(schema_type) => {
+                    let decode = self.write_reqwest_decode_body(content_type);
+                    quote! {
+                        {
+                            let body: $(schema_type) = $(decode);
+                            Ok($(operation.response())::$(variant_name)($(if is_dynamic { s, }) body))
+                        }
+                    }
+                }
                 None => quote! {
-                    Ok($(operation.response())::S$(status_code))
+                    Ok($(operation.response())::$(variant_name)$(if is_dynamic { (s) }))
                 },
             }
         };
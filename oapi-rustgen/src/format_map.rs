@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+/// Maps OpenAPI `format` strings (on `integer`/`number`/`string` schemas) to the Rust type
+/// that should represent them, following schemars' `SchemaSettings` idea of a configurable
+/// generation profile. The defaults reproduce the previously hardcoded numeric mappings and
+/// add the common temporal/identifier/binary formats.
+#[derive(Debug, Clone)]
+pub struct FormatMap {
+    types: BTreeMap<String, String>,
+}
+
+impl FormatMap {
+    pub fn with_format(mut self, format: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.types.insert(format.into(), rust_type.into());
+        self
+    }
+
+    pub fn rust_type_for(&self, format: &str) -> Option<&str> {
+        self.types.get(format).map(|s| s.as_str())
+    }
+}
+
+impl Default for FormatMap {
+    fn default() -> Self {
+        Self {
+            types: BTreeMap::from([
+                ("int32".to_owned(), "i32".to_owned()),
+                ("int64".to_owned(), "i64".to_owned()),
+                ("float".to_owned(), "f32".to_owned()),
+                ("double".to_owned(), "f64".to_owned()),
+                (
+                    "date-time".to_owned(),
+                    "chrono::DateTime<chrono::Utc>".to_owned(),
+                ),
+                ("date".to_owned(), "chrono::NaiveDate".to_owned()),
+                ("uuid".to_owned(), "uuid::Uuid".to_owned()),
+                // carried as plain `Vec<u8>`: no base64 (de)serializer is attached, so serde
+                // reads and writes it as an array of numbers, not a base64 string
+                ("byte".to_owned(), "Vec<u8>".to_owned()),
+                ("binary".to_owned(), "Vec<u8>".to_owned()),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_cover_the_previously_hardcoded_numeric_formats() {
+        let formats = FormatMap::default();
+        assert_eq!(formats.rust_type_for("int32"), Some("i32"));
+        assert_eq!(formats.rust_type_for("int64"), Some("i64"));
+        assert_eq!(formats.rust_type_for("float"), Some("f32"));
+        assert_eq!(formats.rust_type_for("double"), Some("f64"));
+    }
+
+    #[test]
+    fn defaults_cover_temporal_identifier_and_binary_formats() {
+        let formats = FormatMap::default();
+        assert_eq!(
+            formats.rust_type_for("date-time"),
+            Some("chrono::DateTime<chrono::Utc>")
+        );
+        assert_eq!(formats.rust_type_for("date"), Some("chrono::NaiveDate"));
+        assert_eq!(formats.rust_type_for("uuid"), Some("uuid::Uuid"));
+        assert_eq!(formats.rust_type_for("byte"), Some("Vec<u8>"));
+        assert_eq!(formats.rust_type_for("binary"), Some("Vec<u8>"));
+    }
+
+    #[test]
+    fn unknown_format_is_none() {
+        let formats = FormatMap::default();
+        assert_eq!(formats.rust_type_for("not-a-format"), None);
+    }
+
+    #[test]
+    fn with_format_adds_and_overrides_entries() {
+        let formats = FormatMap::default()
+            .with_format("int32", "std::num::NonZeroI32")
+            .with_format("money", "rust_decimal::Decimal");
+        assert_eq!(formats.rust_type_for("int32"), Some("std::num::NonZeroI32"));
+        assert_eq!(formats.rust_type_for("money"), Some("rust_decimal::Decimal"));
+    }
+}
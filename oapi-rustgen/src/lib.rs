@@ -1,15 +1,23 @@
 #![feature(lazy_cell)]
 
 mod analyzer;
+mod bundler;
 mod client_writer;
+mod format_map;
+mod media_type;
 mod pointer;
 mod renamer;
 pub(crate) mod spec;
 mod server_writer;
 mod types_writer;
+mod validation_writer;
 
 pub use analyzer::*;
+pub use bundler::*;
 pub use client_writer::*;
+pub use format_map::*;
+pub use media_type::*;
 pub use renamer::*;
 pub use server_writer::*;
 pub use types_writer::*;
+pub use validation_writer::*;
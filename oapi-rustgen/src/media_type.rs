@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+
+/// How the body of a given media type should be represented in generated Rust code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MediaTypeKind {
+    /// Deserialized/serialized via `serde_json`, using the schema-derived type.
+    Json,
+    /// Deserialized/serialized via `serde_urlencoded`, using the schema-derived type.
+    UrlEncoded,
+    /// Carried as a plain `String`, ignoring the declared schema.
+    PlainText,
+    /// Carried as raw `Vec<u8>`, ignoring the declared schema.
+    Bytes,
+}
+
+/// Maps OpenAPI media type strings (e.g. `application/json`) to a [`MediaTypeKind`],
+/// mirroring how paperclip's `MediaRange` decides which coder handles a content type.
+#[derive(Debug, Clone)]
+pub struct MediaTypeRegistry {
+    kinds: BTreeMap<String, MediaTypeKind>,
+}
+
+impl MediaTypeRegistry {
+    pub fn with_mapping(mut self, content_type: impl Into<String>, kind: MediaTypeKind) -> Self {
+        self.kinds.insert(content_type.into(), kind);
+        self
+    }
+
+    pub fn kind_for(&self, content_type: &str) -> &MediaTypeKind {
+        self.kinds.get(content_type).unwrap_or(&MediaTypeKind::Json)
+    }
+
+    /// Produces the Rust type used to carry a body of `content_type`: `schema_type` (the type
+    /// derived from the declared schema, or `serde_json::Value` without one) for JSON and
+    /// form-urlencoded bodies, `String` for plain text and `Vec<u8>` for raw bytes.
+    pub fn rust_type_for(&self, content_type: &str, schema_type: Option<&str>) -> String {
+        match self.kind_for(content_type) {
+            MediaTypeKind::Json | MediaTypeKind::UrlEncoded => {
+                schema_type.unwrap_or("serde_json::Value").to_owned()
+            }
+            MediaTypeKind::PlainText => "String".to_owned(),
+            MediaTypeKind::Bytes => "Vec<u8>".to_owned(),
+        }
+    }
+}
+
+/// Whether a media type is framed as a stream of individual events/records rather than a
+/// single body -- `text/event-stream` (SSE) and `application/x-ndjson` (newline-delimited
+/// JSON) both carry a sequence of `T` rather than one `T`.
+pub fn is_streaming_media_type(content_type: &str) -> bool {
+    matches!(content_type, "text/event-stream" | "application/x-ndjson")
+}
+
+impl Default for MediaTypeRegistry {
+    fn default() -> Self {
+        Self {
+            kinds: BTreeMap::from([
+                ("application/json".to_owned(), MediaTypeKind::Json),
+                ("text/plain".to_owned(), MediaTypeKind::PlainText),
+                (
+                    "application/octet-stream".to_owned(),
+                    MediaTypeKind::Bytes,
+                ),
+                (
+                    "application/x-www-form-urlencoded".to_owned(),
+                    MediaTypeKind::UrlEncoded,
+                ),
+            ]),
+        }
+    }
+}
@@ -1,144 +1,1235 @@
-use std::collections::BTreeMap;
-
 use derive_more::{Display, Error};
 use genco::{prelude::rust::Tokens, quote, tokens::quoted};
 use http::Method;
 
-use crate::{AnalysisResult, ClientWriter, OperationDef, SegmentOrParameter};
+use crate::{
+    analyzer::{default_status_for, is_dynamic_response_status, response_variant_name, SecuritySchemeKind},
+    spec::{ParameterLocation, SchemaType},
+    AnalysisResult, ClientWriter, CollectionFormat, MediaTypeKind, OperationDef, ParameterDef,
+    SegmentOrParameter,
+};
+
+/// The element type of an `schema_type` rendered as `Vec<T>` by [`AnalysisResult::name_type`],
+/// or `None` if it isn't an array. Cheaper than threading a separate item-type field through
+/// [`ParameterDef`] since array parameters always render this way.
+fn array_item_type(schema_type: &str) -> Option<&str> {
+    schema_type.strip_prefix("Vec<").and_then(|s| s.strip_suffix('>'))
+}
+
+fn collection_format_separator(format: CollectionFormat) -> &'static str {
+    match format {
+        CollectionFormat::Multi => unreachable!("handled separately"),
+        CollectionFormat::Csv => ",",
+        CollectionFormat::Ssv => " ",
+        CollectionFormat::Pipes => "|",
+        CollectionFormat::Tsv => "\t",
+    }
+}
 
 #[derive(Debug, Display, Error)]
 pub enum ServerWriterError {}
 
+/// Which web framework `ServerWriter` emits a server for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ServerBackend {
+    #[default]
+    ActixWeb,
+    Axum,
+}
+
 pub struct ServerWriter<'a> {
     analysis: &'a AnalysisResult,
+    backend: ServerBackend,
 }
 
 impl<'a> ServerWriter<'a> {
     pub fn new(analysis: &'a AnalysisResult) -> Self {
-        ServerWriter { analysis }
+        Self::with_backend(analysis, ServerBackend::ActixWeb)
+    }
+
+    pub fn with_backend(analysis: &'a AnalysisResult, backend: ServerBackend) -> Self {
+        ServerWriter { analysis, backend }
     }
 
     pub fn write(&self) -> Result<Tokens, ServerWriterError> {
+        match self.backend {
+            ServerBackend::ActixWeb => self.write_actix(),
+            ServerBackend::Axum => self.write_axum(),
+        }
+    }
+
+    fn any_operation_requires_auth(&self) -> bool {
+        self.analysis.operations().iter().any(|o| o.requires_auth())
+    }
+
+    /// Excludes streaming operations, whose responses are framed as a sequence of events rather
+    /// than negotiated against `Accept` -- otherwise a spec with only streaming response bodies
+    /// would emit an unused `accept_allows`.
+    fn any_operation_has_response_body(&self) -> bool {
+        self.analysis
+            .operations()
+            .iter()
+            .any(|o| o.streaming_response().is_none() && o.has_any_response_body())
+    }
+
+    /// Emitted once up front when at least one operation has a response body, so its
+    /// encoding can be negotiated against the request's `Accept` header.
+    fn accept_allows_fn(&self) -> Tokens {
+        quote! {
+            /// Whether an `Accept` header (a comma-separated list of media ranges, each
+            /// optionally suffixed with `;q=...`) allows a response of `content_type`. A bare
+            /// `*/*`, a type-level wildcard (`application/*`), or an exact match all count; an
+            /// absent/empty header accepts anything.
+            fn accept_allows(accept: &str, content_type: &str) -> bool {
+                if accept.trim().is_empty() {
+                    return true;
+                }
+                accept.split(',').any(|range| {
+                    let range = range.split(';').next().unwrap_or("").trim();
+                    range == "*/*"
+                        || range == content_type
+                        || range
+                            .strip_suffix("/*")
+                            .is_some_and(|prefix| content_type.starts_with(&format!("{}/", prefix)))
+                })
+            }
+        }
+    }
+
+    /// Emitted once up front when at least one operation declares a `security` requirement.
+    /// Carries whichever single credential an inbound request presented, tagged with the name
+    /// of the scheme it was extracted for so `Handlers::authorize` can tell them apart.
+    fn credential_enum(&self) -> Tokens {
+        quote! {
+            #[derive(Debug, Clone)]
+            pub enum Credential {
+                Bearer { scheme: String, token: String },
+                Basic { scheme: String, user: String, password: String },
+                ApiKey { scheme: String, value: String },
+            }
+        }
+    }
+
+    /// Like [`ClientWriter::write_operation_function_signature`], but for the `Handlers` trait
+    /// method: when `o` requires auth, the resolved `Principal` is threaded in as the first
+    /// argument instead of a credential the handler would otherwise have to extract itself.
+    fn handlers_trait_method(&self, o: &OperationDef) -> Tokens {
+        let return_type = match o.streaming_response() {
+            Some((_, item_type)) => quote! {
+                std::pin::Pin<Box<dyn futures::Stream<Item = Result<$(item_type.to_owned()), Self::Error>>>>
+            },
+            None => quote! { $(o.response()) },
+        };
+        quote! {
+            async fn $(o.name())(
+                &self,
+                $(if o.requires_auth() { principal: Self::Principal, })
+                $(for (_, ty) in o.path_params() join (, ) => $(ty.name()): $(ty.schema_type()))$(if o.path_params().count() > 0 { ,  })
+                $(for p in o.query_params() join (, ) => $(p.name()): $(p.argument_type()))$(if o.query_params().count() > 0 { ,  })
+                $(for p in o.header_params() join (, ) => $(p.name()): $(p.argument_type()))$(if o.header_params().count() > 0 { ,  })
+                $(for p in o.cookie_params() join (, ) => $(p.name()): $(p.argument_type()))$(if o.cookie_params().count() > 0 { ,  })
+                $(if let Some(b) = o.request_body() { body: $(b),  })
+            ) -> Result<$(return_type), Self::Error>
+        }
+    }
+
+    /// Whether `o`'s request body is carried by a schema-derived (rather than `String`/`Vec<u8>`)
+    /// type that actually has a `Validate` impl. `ValidationWriter` only emits `impl Validate` for
+    /// object-rooted schemas, so a JSON/urlencoded body whose root schema is an array, a `oneOf`,
+    /// or a property-less `additionalProperties` map has no `validate()` method to call even
+    /// though its media type looks validatable.
+    fn request_body_is_validatable(&self, o: &OperationDef) -> bool {
+        let media_type_is_validatable = o
+            .request_body_media_type()
+            .map(|content_type| {
+                matches!(
+                    self.analysis.media_types().kind_for(content_type),
+                    MediaTypeKind::Json | MediaTypeKind::UrlEncoded
+                )
+            })
+            .unwrap_or(false);
+        if !media_type_is_validatable {
+            return false;
+        }
+
+        o.request_body()
+            .and_then(|rust_type| self.analysis.schemas().iter().find(|s| s.name() == rust_type))
+            .map(|ty| ty.schema().schema_type == Some(SchemaType::Object) && !ty.schema().properties.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Builds the `.content_type(..).body(..)` (or `.json(..)`) tail appended to a response
+    /// builder, picked by the response's declared media type rather than always JSON-encoding.
+    fn actix_encode_response(&self, content_type: &str, expr: Tokens) -> Tokens {
+        let content_type_quoted = quoted(content_type.to_owned());
+        match self.analysis.media_types().kind_for(content_type) {
+            MediaTypeKind::Json => quote! { .json($(expr)) },
+            MediaTypeKind::UrlEncoded => quote! {
+                .content_type($(content_type_quoted))
+                .body(serde_urlencoded::to_string(&$(expr)).expect("should serialize"))
+            },
+            MediaTypeKind::PlainText | MediaTypeKind::Bytes => quote! {
+                .content_type($(content_type_quoted))
+                .body($(expr))
+            },
+        }
+    }
+
+    /// Reads `o`'s request body as raw bytes, rejects it with `415` if the incoming
+    /// `Content-Type` doesn't match the declared one, then decodes it per its [`MediaTypeKind`].
+    fn actix_request_body_extract(&self, o: &OperationDef) -> Tokens {
+        let Some(rust_type) = o.request_body() else {
+            return Tokens::new();
+        };
+        let content_type = o
+            .request_body_media_type()
+            .expect("a request body always has a media type");
+        let content_type_quoted = quoted(content_type.to_owned());
+        let decode: Tokens = match self.analysis.media_types().kind_for(content_type) {
+            MediaTypeKind::Bytes => quote! {
+                let body: $(rust_type) = body_bytes.to_vec();
+            },
+            MediaTypeKind::Json => quote! {
+                let body: $(rust_type) = match serde_json::from_slice(&body_bytes) {
+                    Ok(body) => body,
+                    Err(e) => return actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::BAD_REQUEST)
+                        .json(serde_json::json!({ "error": format!("{}", e) })),
+                };
+            },
+            MediaTypeKind::UrlEncoded => quote! {
+                let body: $(rust_type) = match serde_urlencoded::from_bytes(&body_bytes) {
+                    Ok(body) => body,
+                    Err(e) => return actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::BAD_REQUEST)
+                        .json(serde_json::json!({ "error": format!("{}", e) })),
+                };
+            },
+            MediaTypeKind::PlainText => quote! {
+                let body: $(rust_type) = match String::from_utf8(body_bytes.to_vec()) {
+                    Ok(body) => body,
+                    Err(e) => return actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::BAD_REQUEST)
+                        .json(serde_json::json!({ "error": format!("{}", e) })),
+                };
+            },
+        };
+        quote! {
+            let content_type = req.headers().get(actix_web::http::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("");
+            if !content_type.starts_with($(&content_type_quoted)) {
+                return actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                    .json(serde_json::json!({ "error": format!("expected content-type `{}`", $(&content_type_quoted)) }));
+            }
+            $(decode)
+        }
+    }
+
+    fn write_actix(&self) -> Result<Tokens, ServerWriterError> {
         let mut tokens = Tokens::new();
+        let requires_auth = self.any_operation_requires_auth();
 
-        tokens.append(quote! { use futures::StreamExt; });
-        tokens.append(quote! { use std::str::FromStr; });
-        tokens.line();
+        if requires_auth {
+            tokens.append(&self.credential_enum());
+            tokens.line();
+        }
+
+        if self.any_operation_has_response_body() {
+            tokens.append(&self.accept_allows_fn());
+            tokens.line();
+        }
 
         let trait_def: Tokens = quote! {
             #[async_trait::async_trait(?Send)]
             pub trait Handlers {
                 type Error: std::fmt::Debug;
+                $(if requires_auth { type Principal; })
 
                 $(for o in &self.analysis.operations() =>
-                    $(ClientWriter::write_operation_function_signature(o));
+                    $(self.handlers_trait_method(o));
                 )
+
+                $(if requires_auth {
+                    /// Resolves a `Credential` extracted for `operation` into the caller's
+                    /// `Principal`, or rejects the request (e.g. with a domain "forbidden"
+                    /// error mapped to `403` by `error_response`).
+                    async fn authorize(&self, operation: &str, credential: Credential) -> Result<Self::Principal, Self::Error>;
+                })
+
+                /// Maps an error returned from a handler to an HTTP response. Overridable so
+                /// domain errors (e.g. "not found") can be reported with something other than
+                /// a `500`.
+                fn error_response(&self, e: Self::Error) -> actix_web::HttpResponse {
+                    actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+                        .json(serde_json::json!({ "error": format!("{:?}", e) }))
+                }
             }
         };
         tokens.append(&trait_def);
         tokens.line();
 
-        let operations_by_path =
-            self.analysis
-                .operations()
-                .into_iter()
-                .fold(BTreeMap::default(), |mut memo, o| {
-                    let entry = memo.entry(o.path().to_owned()).or_insert(vec![]);
-                    entry.push(o);
-                    memo
-                });
-        let trait_impl: Tokens = quote! {
-            pub async fn handler<T, E>(
-                req: actix_web::HttpRequest,
-                mut payload: actix_web::web::Payload,
-            ) -> Result<actix_web::HttpResponse, actix_web::Error>
-            where
-                T: Handlers + actix_web::FromRequest<Error = E>,
-                E: std::fmt::Debug
-            {
-                let handlers = T::extract(&req).await.expect("handler data should be set");
-                let method = req.method();
-                let path: Vec<_> = req.path().split('/').skip(1).collect();
-                let mut body = actix_web::web::BytesMut::new();
-                while let Some(item) = payload.next().await {
-                    body.extend_from_slice(&item.expect("should read"));
-                }
-
-                $(for (path, operations) in &operations_by_path => if let $(self.get_operation_path_match(path)) {
-                    $(for o in operations =>
-                        if method == $(self.get_actix_method(o)) {
-                            $(for (_, p) in o.path_params() => let $(p.name()) = $(p.schema_type())::from_str($(p.name())).expect("should deserialize");)
-                            $(if let Some(body) = o.request_body() {
-                                let body: $(body) = serde_json::from_slice(&body).expect("body should deserialize");
-                            })
-                            let response = handlers.$(o.name())($(for (_, p) in o.path_params() => $(p.name()), )$(if o.request_body().is_some() { body })).await.expect("should execute");
-
-                            $(self.match_responses(o))
-                            
-                        }
-                    )
-                })
+        for o in self.analysis.operations() {
+            tokens.append(&self.write_actix_handler(&o));
+            tokens.line();
+        }
 
-                todo!();
+        let configure_def: Tokens = quote! {
+            /// Mounts every operation as an actix-router resource, e.g. `App::new().configure(|cfg| configure(cfg, handlers))`.
+            ///
+            /// Operations sharing a path are grouped onto a single `resource()`, each as its own
+            /// `.route()`, so actix-router's literal-over-pattern matching and its built-in
+            /// `404`/`405` (with an `Allow` header listing the methods registered on that
+            /// resource) apply correctly -- registering the same path as separate resources would
+            /// leave all but the first unreachable. Wrap the `App` in
+            /// `actix_web::middleware::NormalizePath::trim()` for trailing-slash-insensitive matching.
+            pub fn configure<T: Handlers + Clone + 'static>(cfg: &mut actix_web::web::ServiceConfig, handlers: T) {
+                cfg.app_data(actix_web::web::Data::new(handlers));
+                $(for (path, ops) in self.group_operations_by_actix_path() =>
+                    cfg.service(
+                        actix_web::web::resource($(quoted(path)))
+                            $(for o in &ops => .route(actix_web::web::$(self.actix_method(o))().to($(self.actix_handler_fn_name(o))::<T>)))
+                    );
+                )
             }
         };
-        tokens.append(&trait_impl);
+        tokens.append(&configure_def);
         tokens.line();
 
         Ok(tokens)
     }
 
-    fn get_operation_path_match(&self, path: &[SegmentOrParameter]) -> Tokens {
-        quote! { &[$(for s in path join (, ) => $(match s {
-            SegmentOrParameter::Segment(s) => $(quoted(s)),
-            SegmentOrParameter::Parameter(p) => $(p),
-        }))] = path.as_slice() }
+    /// Groups operations that render to an identical actix path pattern, preserving the order
+    /// each path was first seen in. actix-web expects one `Resource` per path with a `.route()`
+    /// per method, not a separate `Resource` per method on the same path -- the latter leaves
+    /// every operation but the first-registered one for that path unreachable.
+    fn group_operations_by_actix_path(&self) -> Vec<(String, Vec<OperationDef>)> {
+        let mut groups: Vec<(String, Vec<OperationDef>)> = vec![];
+        for o in self.analysis.operations() {
+            let path = self.actix_path(&o);
+            match groups.iter_mut().find(|(p, _)| *p == path) {
+                Some((_, ops)) => ops.push(o),
+                None => groups.push((path, vec![o])),
+            }
+        }
+        groups
     }
 
-    fn get_actix_method(&self, o: &OperationDef) -> Tokens {
+    /// actix-web already uses `{param}` path syntax, so the OpenAPI pattern carries over unchanged.
+    fn actix_path(&self, o: &OperationDef) -> String {
+        o.path().iter().fold(String::new(), |memo, s| match s {
+            SegmentOrParameter::Segment(s) if s.is_empty() => memo,
+            SegmentOrParameter::Segment(s) => format!("{}/{}", memo, s),
+            SegmentOrParameter::Parameter(p) => format!("{}/{{{}}}", memo, p),
+        })
+    }
+
+    fn actix_method(&self, o: &OperationDef) -> Tokens {
         match *o.method() {
-            Method::CONNECT => quote! { actix_web::http::Method::CONNECT },
-            Method::DELETE => quote! { actix_web::http::Method::DELETE },
-            Method::GET => quote! { actix_web::http::Method::GET },
-            Method::HEAD => quote! { actix_web::http::Method::HEAD },
-            Method::OPTIONS => quote! { actix_web::http::Method::OPTIONS },
-            Method::PATCH => quote! { actix_web::http::Method::PATCH },
-            Method::POST => quote! { actix_web::http::Method::POST },
-            Method::PUT => quote! { actix_web::http::Method::PUT },
-            Method::TRACE => quote! { actix_web::http::Method::TRACE },
+            Method::CONNECT => quote! { connect },
+            Method::DELETE => quote! { delete },
+            Method::GET => quote! { get },
+            Method::HEAD => quote! { head },
+            Method::OPTIONS => quote! { options },
+            Method::PATCH => quote! { patch },
+            Method::POST => quote! { post },
+            Method::PUT => quote! { put },
+            Method::TRACE => quote! { trace },
             _ => panic!("unknown method `{:?}` for actix_web", o.method()),
         }
     }
 
+    fn actix_handler_fn_name(&self, o: &OperationDef) -> String {
+        format!("{}_handler", o.name())
+    }
+
+    fn write_actix_handler(&self, o: &OperationDef) -> Tokens {
+        if o.streaming_response().is_some() {
+            return self.write_actix_streaming_handler(o);
+        }
+
+        let has_path_params = o.path_params().count() > 0;
+        let needs_request = o.header_params().count() > 0
+            || o.cookie_params().count() > 0
+            || o.query_params().count() > 0
+            || o.requires_auth()
+            || o.request_body().is_some()
+            || o.has_any_response_body();
+        quote! {
+            async fn $(self.actix_handler_fn_name(o))<T: Handlers + 'static>(
+                handlers: actix_web::web::Data<T>,
+                $(if needs_request { req: actix_web::HttpRequest, })
+                $(if has_path_params {
+                    path: actix_web::web::Path<($(for (_, _p) in o.path_params() join (, ) => String,)) >,
+                })
+                $(if o.request_body().is_some() { body_bytes: actix_web::web::Bytes, })
+            ) -> actix_web::HttpResponse {
+                $(if o.requires_auth() {
+                    let credential: Option<Credential> = None
+                        $(for name in o.security() => $(self.actix_try_credential(name)));
+                    let principal = match credential {
+                        Some(credential) => match handlers.authorize($(quoted(o.name().to_owned())), credential).await {
+                            Ok(principal) => principal,
+                            Err(e) => return handlers.error_response(e),
+                        },
+                        None => return actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::UNAUTHORIZED).finish(),
+                    };
+                })
+                $(if has_path_params {
+                    let ($(for (_, p) in o.path_params() join (, ) => $(format!("{}_raw", p.name()))),) = path.into_inner();
+                    $(for (_, p) in o.path_params() => $(self.actix_path_extract(p)))
+                })
+                $(if o.query_params().count() > 0 {
+                    let query_pairs: Vec<(String, String)> = serde_urlencoded::from_str(req.query_string()).unwrap_or_default();
+                })
+                $(for p in o.query_params() => $(self.actix_query_extract(p)))
+                $(for p in o.header_params() => $(self.actix_header_extract(p)))
+                $(for p in o.cookie_params() => $(self.actix_cookie_extract(p)))
+                $(self.actix_request_body_extract(o))
+                $(if self.request_body_is_validatable(o) {
+                    if let Err(validation_errors) = body.validate() {
+                        return actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::BAD_REQUEST).json(validation_errors);
+                    }
+                })
+                let response = match handlers.$(o.name())(
+                    $(if o.requires_auth() { principal, })
+                    $(for (_, p) in o.path_params() join (, ) => $(p.name()))$(if o.path_params().count() > 0 { ,  })
+                    $(for p in o.query_params() join (, ) => $(p.name()))$(if o.query_params().count() > 0 { ,  })
+                    $(for p in o.header_params() join (, ) => $(p.name()))$(if o.header_params().count() > 0 { ,  })
+                    $(for p in o.cookie_params() join (, ) => $(p.name()))$(if o.cookie_params().count() > 0 { ,  })
+                    $(if o.request_body().is_some() { body })
+                ).await {
+                    Ok(r) => r,
+                    Err(e) => return handlers.error_response(e),
+                };
+
+                $(self.match_responses(o))
+            }
+        }
+    }
+
+    /// Mirrors `ServerWriter::write_axum_streaming_handler`: a streaming (SSE/ndjson) operation's
+    /// `Handlers` method returns a `Stream` rather than a single response value, so it needs its
+    /// own handler shape instead of going through `match_responses`/`actix_encode_response`,
+    /// which assume a single already-resolved body. Frames each item as an SSE `data:` event and
+    /// streams the body rather than buffering it.
+    fn write_actix_streaming_handler(&self, o: &OperationDef) -> Tokens {
+        let has_path_params = o.path_params().count() > 0;
+        quote! {
+            async fn $(self.actix_handler_fn_name(o))<T: Handlers + 'static>(
+                handlers: actix_web::web::Data<T>,
+                $(if has_path_params {
+                    path: actix_web::web::Path<($(for (_, _p) in o.path_params() join (, ) => String,)) >,
+                })
+            ) -> actix_web::HttpResponse {
+                $(if has_path_params {
+                    let ($(for (_, p) in o.path_params() join (, ) => $(format!("{}_raw", p.name()))),) = path.into_inner();
+                    $(for (_, p) in o.path_params() => $(self.actix_path_extract(p)))
+                })
+                let stream = match handlers.$(o.name())($(for (_, p) in o.path_params() join (, ) => $(p.name()))).await {
+                    Ok(s) => s,
+                    Err(e) => return handlers.error_response(e),
+                };
+                let stream = futures::StreamExt::map(stream, |item| {
+                    let data = item.map(|i| serde_json::to_string(&i).expect("should serialize")).unwrap_or_default();
+                    Ok::<_, actix_web::Error>(actix_web::web::Bytes::from(format!("data: {}\n\n", data)))
+                });
+                actix_web::HttpResponse::Ok().content_type("text/event-stream").streaming(stream)
+            }
+        }
+    }
+
+    /// Tries extracting a `Credential` for one of `o`'s accepted scheme names, falling back to
+    /// whatever `credential` already held (so the first scheme that successfully extracts wins).
+    fn actix_try_credential(&self, name: &str) -> Tokens {
+        let Some(scheme) = self.analysis.security_schemes().get(name).cloned() else {
+            return Tokens::new();
+        };
+        let scheme_name = quoted(scheme.name().to_owned());
+        let extract: Tokens = match scheme.kind() {
+            SecuritySchemeKind::Bearer => quote! {
+                req.headers().get(actix_web::http::header::AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Bearer "))
+                    .map(|token| Credential::Bearer { scheme: $(&scheme_name).to_owned(), token: token.to_owned() })
+            },
+            SecuritySchemeKind::Basic => quote! {
+                req.headers().get(actix_web::http::header::AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Basic "))
+                    .and_then(|v| { use base64::Engine; base64::engine::general_purpose::STANDARD.decode(v).ok() })
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .and_then(|decoded| decoded.split_once(':').map(|(u, p)| (u.to_owned(), p.to_owned())))
+                    .map(|(user, password)| Credential::Basic { scheme: $(&scheme_name).to_owned(), user, password })
+            },
+            SecuritySchemeKind::ApiKey { location: ParameterLocation::Header, name: key_name } => {
+                let key_name = quoted(key_name.to_owned());
+                quote! {
+                    req.headers().get($(&key_name)).and_then(|v| v.to_str().ok())
+                        .map(|v| Credential::ApiKey { scheme: $(&scheme_name).to_owned(), value: v.to_owned() })
+                }
+            }
+            SecuritySchemeKind::ApiKey { location: ParameterLocation::Cookie, name: key_name } => {
+                let key_name = quoted(key_name.to_owned());
+                quote! {
+                    req.cookie($(&key_name)).map(|c| Credential::ApiKey { scheme: $(&scheme_name).to_owned(), value: c.value().to_owned() })
+                }
+            }
+            SecuritySchemeKind::ApiKey { name: key_name, .. } => {
+                let key_name = quoted(key_name.to_owned());
+                quote! {
+                    actix_web::web::Query::<std::collections::HashMap<String, String>>::from_query(req.query_string()).ok()
+                        .and_then(|q| q.get($(&key_name)).cloned())
+                        .map(|value| Credential::ApiKey { scheme: $(&scheme_name).to_owned(), value })
+                }
+            }
+        };
+        quote! { .or_else(|| $(extract)) }
+    }
+
+    fn actix_bad_request(&self, kind: &str, name: &str) -> Tokens {
+        quote! {
+            actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::BAD_REQUEST)
+                .json(serde_json::json!({ "error": format!("invalid value for {} `{}`", $(quoted(kind.to_owned())), $(quoted(name.to_owned()))) }))
+        }
+    }
+
+    fn actix_missing_required(&self, kind: &str, name: &str) -> Tokens {
+        quote! {
+            actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::BAD_REQUEST)
+                .json(serde_json::json!({ "error": format!("missing required {} `{}`", $(quoted(kind.to_owned())), $(quoted(name.to_owned()))) }))
+        }
+    }
+
+    /// actix-web's `Path<T>` extractor rejects a malformed segment with a `404` (it reads as
+    /// "no such route"), not the `400` a bad request argument should get. Capturing path
+    /// segments as `String` and parsing them here ourselves gets the status code right.
+    fn actix_path_extract(&self, p: &ParameterDef) -> Tokens {
+        let bad_request = self.actix_bad_request("path parameter", p.name());
+        let raw = format!("{}_raw", p.name());
+        quote! {
+            let $(p.name()): $(p.schema_type()) = match $(raw).parse() {
+                Ok(v) => v,
+                Err(_) => return $(bad_request),
+            };
+        }
+    }
+
+    /// `query_pairs` (built once per handler from the raw query string) can carry a key more
+    /// than once, which a `HashMap`-based extractor can't represent -- needed for `Vec<T>`
+    /// parameters using `CollectionFormat::Multi` (`?tag=a&tag=b`) as well as the
+    /// separator-joined forms (`?tag=a,b`).
+    fn actix_query_extract(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        let bad_request = self.actix_bad_request("query parameter", p.name());
+        let missing = self.actix_missing_required("query parameter", p.name());
+        match array_item_type(p.schema_type()) {
+            Some(item_type) if p.collection_format() != Some(CollectionFormat::Multi) => {
+                let separator = quoted(collection_format_separator(
+                    p.collection_format().unwrap_or(CollectionFormat::Csv),
+                ));
+                if p.required() {
+                    quote! {
+                        let $(p.name()): Vec<$(item_type)> = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                            Some((_, v)) => match v.split($(separator)).map(|s| s.parse()).collect() {
+                                Ok(v) => v,
+                                Err(_) => return $(bad_request),
+                            },
+                            None => return $(missing),
+                        };
+                    }
+                } else {
+                    quote! {
+                        let $(p.name()): Option<Vec<$(item_type)>> = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                            Some((_, v)) => match v.split($(separator)).map(|s| s.parse()).collect() {
+                                Ok(v) => Some(v),
+                                Err(_) => return $(bad_request),
+                            },
+                            None => None,
+                        };
+                    }
+                }
+            }
+            Some(item_type) => {
+                if p.required() {
+                    quote! {
+                        let $(format!("{}_values", p.name())): Vec<&str> = query_pairs.iter().filter(|(k, _)| k.as_str() == $(&name)).map(|(_, v)| v.as_str()).collect();
+                        if $(format!("{}_values", p.name())).is_empty() {
+                            return $(missing);
+                        }
+                        let $(p.name()): Vec<$(item_type)> = match $(format!("{}_values", p.name())).iter().map(|v| v.parse()).collect() {
+                            Ok(v) => v,
+                            Err(_) => return $(bad_request),
+                        };
+                    }
+                } else {
+                    quote! {
+                        let $(format!("{}_values", p.name())): Vec<&str> = query_pairs.iter().filter(|(k, _)| k.as_str() == $(&name)).map(|(_, v)| v.as_str()).collect();
+                        let $(p.name()): Option<Vec<$(item_type)>> = if $(format!("{}_values", p.name())).is_empty() {
+                            None
+                        } else {
+                            match $(format!("{}_values", p.name())).iter().map(|v| v.parse()).collect() {
+                                Ok(v) => Some(v),
+                                Err(_) => return $(bad_request),
+                            }
+                        };
+                    }
+                }
+            }
+            None if p.required() => quote! {
+                let $(p.name()): $(p.schema_type()) = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                    Some((_, v)) => match v.parse() {
+                        Ok(v) => v,
+                        Err(_) => return $(bad_request),
+                    },
+                    None => return $(missing),
+                };
+            },
+            None => quote! {
+                let $(p.name()): Option<$(p.schema_type())> = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                    Some((_, v)) => match v.parse() {
+                        Ok(v) => Some(v),
+                        Err(_) => return $(bad_request),
+                    },
+                    None => None,
+                };
+            },
+        }
+    }
+
+    fn actix_header_extract(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        let bad_request = self.actix_bad_request("header", p.name());
+        if p.required() {
+            let missing = self.actix_missing_required("header", p.name());
+            quote! {
+                let $(p.name()): $(p.schema_type()) = match req.headers().get($(&name)).and_then(|v| v.to_str().ok()) {
+                    Some(v) => match v.parse() {
+                        Ok(v) => v,
+                        Err(_) => return $(bad_request),
+                    },
+                    None => return $(missing),
+                };
+            }
+        } else {
+            quote! {
+                let $(p.name()): Option<$(p.schema_type())> = match req.headers().get($(&name)).and_then(|v| v.to_str().ok()) {
+                    Some(v) => match v.parse() {
+                        Ok(v) => Some(v),
+                        Err(_) => return $(bad_request),
+                    },
+                    None => None,
+                };
+            }
+        }
+    }
+
+    fn actix_cookie_extract(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        let bad_request = self.actix_bad_request("cookie", p.name());
+        if p.required() {
+            let missing = self.actix_missing_required("cookie", p.name());
+            quote! {
+                let $(p.name()): $(p.schema_type()) = match req.cookie($(&name)) {
+                    Some(c) => match c.value().parse() {
+                        Ok(v) => v,
+                        Err(_) => return $(bad_request),
+                    },
+                    None => return $(missing),
+                };
+            }
+        } else {
+            quote! {
+                let $(p.name()): Option<$(p.schema_type())> = match req.cookie($(&name)) {
+                    Some(c) => match c.value().parse() {
+                        Ok(v) => Some(v),
+                        Err(_) => return $(bad_request),
+                    },
+                    None => None,
+                };
+            }
+        }
+    }
+
     fn match_responses(&self, operation: &OperationDef) -> Tokens {
         if operation.responses().len() == 1 {
             let (status_code, _) = operation.responses().first_key_value().expect("length 1");
-            let status_code: u16 = status_code.parse().unwrap_or(500);
+            let status_code_num = default_status_for(status_code);
             if operation.response() == "()" {
                 quote! {
-                    return Ok(actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16($(status_code)).expect("valid status code")).finish());
+                    actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16($(status_code_num)).expect("valid status code")).finish()
                 }
             } else {
+                let content_type = operation.response_media_type(status_code).unwrap_or("application/json");
+                let content_type_quoted = quoted(content_type.to_owned());
+                let body = self.actix_encode_response(content_type, quote!(response));
                 quote! {
-                    return Ok(actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16($(status_code)).expect("valid status code")).json(body));
+                    if !accept_allows(req.headers().get(actix_web::http::header::ACCEPT).and_then(|v| v.to_str().ok()).unwrap_or(""), $(&content_type_quoted)) {
+                        actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::NOT_ACCEPTABLE).finish()
+                    } else {
+                        actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16($(status_code_num)).expect("valid status code"))$(body)
+                    }
                 }
             }
         } else {
             let match_arms: Vec<_> = operation.responses().iter().map(|(status_code, schema)| {
-                let status_code_i: u16 = status_code.parse().unwrap_or(500);
+                let variant_name = response_variant_name(status_code);
+                let is_dynamic = is_dynamic_response_status(status_code);
+                let status_expr: Tokens = if is_dynamic {
+                    quote! { s }
+                } else {
+                    let status_code_i: u16 = status_code.parse().unwrap_or(500);
+                    quote! { $(status_code_i) }
+                };
+                let content_type = operation.response_media_type(status_code).unwrap_or("application/json");
+                let content_type_quoted = quoted(content_type.to_owned());
+                let body = self.actix_encode_response(content_type, quote!(body));
                 quote! {
-                    $(operation.response())::S$(status_code)$(if schema.is_some() { (body) }) => {
-                        return Ok(actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16($(status_code_i))
-                            .expect("valid status code"))
-                            $(if schema.is_some() { .json(body) } else { .finish() }));
+                    $(operation.response())::$(&variant_name)
+                    $(if is_dynamic || schema.is_some() {
+                        ($(if is_dynamic { s$(if schema.is_some() { , }) }) $(if schema.is_some() { body }))
+                    }) => {
+                        $(if schema.is_some() {
+                            if !accept_allows(req.headers().get(actix_web::http::header::ACCEPT).and_then(|v| v.to_str().ok()).unwrap_or(""), $(&content_type_quoted)) {
+                                actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::NOT_ACCEPTABLE).finish()
+                            } else {
+                                actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16($(&status_expr)).unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR))$(body)
+                            }
+                        } else {
+                            actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16($(&status_expr)).unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)).finish()
+                        })
                     },
                 }
             }).collect();
             quote! {
                 match response {
                     $(for match_arm in &match_arms => $(match_arm))
+                    $(operation.response())::Unknown(s, body) => {
+                        actix_web::HttpResponseBuilder::new(actix_web::http::StatusCode::from_u16(s).unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)).json(body)
+                    },
                 }
             }
         }
     }
+
+    /// Builds the `.content_type(..).body(..)` (or `axum::Json(..)`) tail used to build a
+    /// response, picked by the response's declared media type rather than always JSON-encoding.
+    fn axum_encode_response(&self, content_type: &str, expr: Tokens) -> Tokens {
+        let content_type_quoted = quoted(content_type.to_owned());
+        match self.analysis.media_types().kind_for(content_type) {
+            MediaTypeKind::Json => quote! { axum::Json($(expr)).into_response() },
+            MediaTypeKind::UrlEncoded => quote! {
+                (
+                    [(axum::http::header::CONTENT_TYPE, $(content_type_quoted))],
+                    serde_urlencoded::to_string(&$(expr)).expect("should serialize"),
+                ).into_response()
+            },
+            MediaTypeKind::PlainText | MediaTypeKind::Bytes => quote! {
+                ([(axum::http::header::CONTENT_TYPE, $(content_type_quoted))], $(expr)).into_response()
+            },
+        }
+    }
+
+    /// Reads `o`'s request body as raw bytes, rejects it with `415` if the incoming
+    /// `Content-Type` doesn't match the declared one, then decodes it per its [`MediaTypeKind`].
+    fn axum_request_body_extract(&self, o: &OperationDef) -> Tokens {
+        let Some(rust_type) = o.request_body() else {
+            return Tokens::new();
+        };
+        let content_type = o
+            .request_body_media_type()
+            .expect("a request body always has a media type");
+        let content_type_quoted = quoted(content_type.to_owned());
+        let decode: Tokens = match self.analysis.media_types().kind_for(content_type) {
+            MediaTypeKind::Bytes => quote! {
+                let body: $(rust_type) = body_bytes.to_vec();
+            },
+            MediaTypeKind::Json => quote! {
+                let body: $(rust_type) = match serde_json::from_slice(&body_bytes) {
+                    Ok(body) => body,
+                    Err(e) => return (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({ "error": format!("{}", e) }))).into_response(),
+                };
+            },
+            MediaTypeKind::UrlEncoded => quote! {
+                let body: $(rust_type) = match serde_urlencoded::from_bytes(&body_bytes) {
+                    Ok(body) => body,
+                    Err(e) => return (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({ "error": format!("{}", e) }))).into_response(),
+                };
+            },
+            MediaTypeKind::PlainText => quote! {
+                let body: $(rust_type) = match String::from_utf8(body_bytes.to_vec()) {
+                    Ok(body) => body,
+                    Err(e) => return (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({ "error": format!("{}", e) }))).into_response(),
+                };
+            },
+        };
+        quote! {
+            let content_type = headers.get(axum::http::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("");
+            if !content_type.starts_with($(&content_type_quoted)) {
+                return (axum::http::StatusCode::UNSUPPORTED_MEDIA_TYPE, axum::Json(serde_json::json!({ "error": format!("expected content-type `{}`", $(&content_type_quoted)) }))).into_response();
+            }
+            $(decode)
+        }
+    }
+
+    fn write_axum(&self) -> Result<Tokens, ServerWriterError> {
+        let mut tokens = Tokens::new();
+        let requires_auth = self.any_operation_requires_auth();
+
+        if requires_auth {
+            tokens.append(&self.credential_enum());
+            tokens.line();
+        }
+
+        if self.any_operation_has_response_body() {
+            tokens.append(&self.accept_allows_fn());
+            tokens.line();
+        }
+
+        let trait_def: Tokens = quote! {
+            #[async_trait::async_trait]
+            pub trait Handlers: Send + Sync {
+                type Error: std::fmt::Debug;
+                $(if requires_auth { type Principal: Send; })
+
+                $(for o in &self.analysis.operations() =>
+                    $(self.handlers_trait_method(o));
+                )
+
+                $(if requires_auth {
+                    /// Resolves a `Credential` extracted for `operation` into the caller's
+                    /// `Principal`, or rejects the request (e.g. with a domain "forbidden"
+                    /// error mapped to `403` by `error_response`).
+                    async fn authorize(&self, operation: &str, credential: Credential) -> Result<Self::Principal, Self::Error>;
+                })
+
+                /// Maps an error returned from a handler to an HTTP response. Overridable so
+                /// domain errors (e.g. "not found") can be reported with something other than
+                /// a `500`.
+                fn error_response(&self, e: Self::Error) -> axum::response::Response {
+                    use axum::response::IntoResponse;
+                    (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(serde_json::json!({ "error": format!("{:?}", e) }))).into_response()
+                }
+            }
+        };
+        tokens.append(&trait_def);
+        tokens.line();
+
+        for o in self.analysis.operations() {
+            tokens.append(&self.write_axum_handler(&o));
+            tokens.line();
+        }
+
+        let router_def: Tokens = quote! {
+            /// Mounts every operation onto an `axum::Router`. Unlike actix-web's resource table,
+            /// axum already merges repeated `.route()` calls for the same path into one
+            /// `MethodRouter`, so operations sharing a path don't need special-casing here --
+            /// axum's own matchit-based router handles literal-over-pattern precedence and
+            /// returns `404`/`405` (with `Allow`) for unmatched paths/methods on its own.
+            pub fn router<T: Handlers + 'static>(handlers: std::sync::Arc<T>) -> axum::Router {
+                axum::Router::new()
+                    $(for o in &self.analysis.operations() =>
+                        .route($(quoted(self.axum_path(&o))), axum::routing::$(self.axum_method(&o))($(self.axum_handler_fn_name(&o))::<T>))
+                    )
+                    .with_state(handlers)
+            }
+        };
+        tokens.append(&router_def);
+        tokens.line();
+
+        Ok(tokens)
+    }
+
+    /// Rewrites an OpenAPI path (`/pets/{id}`) into axum's path syntax (`/pets/:id`).
+    fn axum_path(&self, o: &OperationDef) -> String {
+        o.path().iter().fold(String::new(), |memo, s| match s {
+            SegmentOrParameter::Segment(s) if s.is_empty() => memo,
+            SegmentOrParameter::Segment(s) => format!("{}/{}", memo, s),
+            SegmentOrParameter::Parameter(p) => format!("{}/:{}", memo, p),
+        })
+    }
+
+    fn axum_method(&self, o: &OperationDef) -> Tokens {
+        match *o.method() {
+            Method::CONNECT => quote! { connect },
+            Method::DELETE => quote! { delete },
+            Method::GET => quote! { get },
+            Method::HEAD => quote! { head },
+            Method::OPTIONS => quote! { options },
+            Method::PATCH => quote! { patch },
+            Method::POST => quote! { post },
+            Method::PUT => quote! { put },
+            Method::TRACE => quote! { trace },
+            _ => panic!("unknown method `{:?}` for axum", o.method()),
+        }
+    }
+
+    fn axum_handler_fn_name(&self, o: &OperationDef) -> String {
+        format!("{}_handler", o.name())
+    }
+
+    /// Binds `o`'s path params as a handler argument. `axum::extract::Path<T>` only supports
+    /// deserializing into a bare scalar `T` when the route captures exactly one segment --
+    /// with two or more path params it needs a `Path<(T1, T2, ...)>` tuple instead, destructured
+    /// the same way directly in the function argument pattern.
+    fn axum_path_extractor(&self, o: &OperationDef) -> Tokens {
+        let path_params: Vec<_> = o.path_params().collect();
+        match path_params.as_slice() {
+            [] => Tokens::new(),
+            [(_, p)] => quote! {
+                axum::extract::Path($(p.name())): axum::extract::Path<$(p.schema_type())>,
+            },
+            params => quote! {
+                axum::extract::Path(($(for (_, p) in params join (, ) => $(p.name())))): axum::extract::Path<($(for (_, p) in params join (, ) => $(p.schema_type())))>,
+            },
+        }
+    }
+
+    fn write_axum_handler(&self, o: &OperationDef) -> Tokens {
+        if o.streaming_response().is_some() {
+            return self.write_axum_streaming_handler(o);
+        }
+
+        let needs_headers = o.header_params().count() > 0
+            || o.cookie_params().count() > 0
+            || o.requires_auth()
+            || o.request_body().is_some()
+            || o.has_any_response_body();
+        let needs_cookies = o.cookie_params().count() > 0;
+        quote! {
+            async fn $(self.axum_handler_fn_name(o))<T: Handlers + 'static>(
+                axum::extract::State(handlers): axum::extract::State<std::sync::Arc<T>>,
+                $(self.axum_path_extractor(o))
+                $(if o.query_params().count() > 0 {
+                    axum::extract::RawQuery(raw_query): axum::extract::RawQuery,
+                })
+                $(if o.requires_auth() {
+                    axum::extract::Query(auth_query): axum::extract::Query<std::collections::HashMap<String, String>>,
+                })
+                $(if needs_headers { headers: axum::http::HeaderMap, })
+                $(if o.request_body().is_some() { body_bytes: axum::body::Bytes, })
+            ) -> axum::response::Response {
+                use axum::response::IntoResponse;
+                $(if o.query_params().count() > 0 {
+                    let query_pairs: Vec<(String, String)> = raw_query.as_deref()
+                        .map(|q| serde_urlencoded::from_str(q).unwrap_or_default())
+                        .unwrap_or_default();
+                })
+                $(self.axum_request_body_extract(o))
+                $(if self.request_body_is_validatable(o) {
+                    if let Err(validation_errors) = body.validate() {
+                        return (axum::http::StatusCode::BAD_REQUEST, axum::Json(validation_errors)).into_response();
+                    }
+                })
+                $(if needs_cookies {
+                    let cookies: std::collections::HashMap<&str, &str> = headers.get(axum::http::header::COOKIE)
+                        .and_then(|v| v.to_str().ok())
+                        .map(|v| v.split("; ").filter_map(|kv| kv.split_once('=')).collect())
+                        .unwrap_or_default();
+                })
+                $(if o.requires_auth() {
+                    let credential: Option<Credential> = None
+                        $(for name in o.security() => $(self.axum_try_credential(name)));
+                    let principal = match credential {
+                        Some(credential) => match handlers.authorize($(quoted(o.name().to_owned())), credential).await {
+                            Ok(principal) => principal,
+                            Err(e) => return handlers.error_response(e),
+                        },
+                        None => return axum::http::StatusCode::UNAUTHORIZED.into_response(),
+                    };
+                })
+                $(for p in o.query_params() => $(self.axum_query_extract(p)))
+                $(for p in o.header_params() => $(self.axum_header_extract(p)))
+                $(for p in o.cookie_params() => $(self.axum_cookie_extract(p)))
+                let response = match handlers.$(o.name())(
+                    $(if o.requires_auth() { principal, })
+                    $(for (_, p) in o.path_params() join (, ) => $(p.name()))$(if o.path_params().count() > 0 { ,  })
+                    $(for p in o.query_params() join (, ) => $(p.name()))$(if o.query_params().count() > 0 { ,  })
+                    $(for p in o.header_params() join (, ) => $(p.name()))$(if o.header_params().count() > 0 { ,  })
+                    $(for p in o.cookie_params() join (, ) => $(p.name()))$(if o.cookie_params().count() > 0 { ,  })
+                    $(if o.request_body().is_some() { body })
+                ).await {
+                    Ok(r) => r,
+                    Err(e) => return handlers.error_response(e),
+                };
+
+                $(self.axum_into_response(o))
+            }
+        }
+    }
+
+    /// Tries extracting a `Credential` for one of `o`'s accepted scheme names, falling back to
+    /// whatever `credential` already held (so the first scheme that successfully extracts wins).
+    fn axum_try_credential(&self, name: &str) -> Tokens {
+        let Some(scheme) = self.analysis.security_schemes().get(name).cloned() else {
+            return Tokens::new();
+        };
+        let scheme_name = quoted(scheme.name().to_owned());
+        let extract: Tokens = match scheme.kind() {
+            SecuritySchemeKind::Bearer => quote! {
+                headers.get(axum::http::header::AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Bearer "))
+                    .map(|token| Credential::Bearer { scheme: $(&scheme_name).to_owned(), token: token.to_owned() })
+            },
+            SecuritySchemeKind::Basic => quote! {
+                headers.get(axum::http::header::AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Basic "))
+                    .and_then(|v| { use base64::Engine; base64::engine::general_purpose::STANDARD.decode(v).ok() })
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .and_then(|decoded| decoded.split_once(':').map(|(u, p)| (u.to_owned(), p.to_owned())))
+                    .map(|(user, password)| Credential::Basic { scheme: $(&scheme_name).to_owned(), user, password })
+            },
+            SecuritySchemeKind::ApiKey { location: ParameterLocation::Header, name: key_name } => {
+                let key_name = quoted(key_name.to_owned());
+                quote! {
+                    headers.get($(&key_name)).and_then(|v| v.to_str().ok())
+                        .map(|v| Credential::ApiKey { scheme: $(&scheme_name).to_owned(), value: v.to_owned() })
+                }
+            }
+            SecuritySchemeKind::ApiKey { location: ParameterLocation::Cookie, name: key_name } => {
+                let key_name = quoted(key_name.to_owned());
+                quote! {
+                    headers.get(axum::http::header::COOKIE)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.split("; ").filter_map(|kv| kv.split_once('=')).find(|(k, _)| *k == $(&key_name)))
+                        .map(|(_, v)| Credential::ApiKey { scheme: $(&scheme_name).to_owned(), value: v.to_owned() })
+                }
+            }
+            SecuritySchemeKind::ApiKey { name: key_name, .. } => {
+                let key_name = quoted(key_name.to_owned());
+                quote! {
+                    auth_query.get($(&key_name)).cloned()
+                        .map(|value| Credential::ApiKey { scheme: $(&scheme_name).to_owned(), value })
+                }
+            }
+        };
+        quote! { .or_else(|| $(extract)) }
+    }
+
+    /// SSE handler: wraps the `Handlers` stream in `axum::response::sse::Sse` with keep-alive,
+    /// encoding each item as a `data:`-framed JSON event.
+    fn write_axum_streaming_handler(&self, o: &OperationDef) -> Tokens {
+        quote! {
+            async fn $(self.axum_handler_fn_name(o))<T: Handlers + 'static>(
+                axum::extract::State(handlers): axum::extract::State<std::sync::Arc<T>>,
+                $(self.axum_path_extractor(o))
+            ) -> axum::response::Response {
+                use axum::response::IntoResponse;
+                let stream = match handlers.$(o.name())($(for (_, p) in o.path_params() join (, ) => $(p.name()))).await {
+                    Ok(s) => s,
+                    Err(e) => return handlers.error_response(e),
+                };
+                let stream = futures::StreamExt::map(stream, |item| {
+                    let event = item.map(|i| axum::response::sse::Event::default().json_data(i).expect("should serialize"));
+                    Ok::<_, std::convert::Infallible>(event.unwrap_or_else(|_| axum::response::sse::Event::default()))
+                });
+                axum::response::sse::Sse::new(stream).keep_alive(axum::response::sse::KeepAlive::default()).into_response()
+            }
+        }
+    }
+
+    fn axum_bad_request(&self, kind: &str, name: &str) -> Tokens {
+        quote! {
+            (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({ "error": format!("invalid value for {} `{}`", $(quoted(kind.to_owned())), $(quoted(name.to_owned()))) }))).into_response()
+        }
+    }
+
+    fn axum_missing_required(&self, kind: &str, name: &str) -> Tokens {
+        quote! {
+            (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({ "error": format!("missing required {} `{}`", $(quoted(kind.to_owned())), $(quoted(name.to_owned()))) }))).into_response()
+        }
+    }
+
+    /// Mirrors `ServerWriter::actix_query_extract` -- `query_pairs` can carry a key more than
+    /// once, which a `HashMap`-based extractor can't represent, needed for `Vec<T>` parameters.
+    fn axum_query_extract(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        let bad_request = self.axum_bad_request("query parameter", p.name());
+        let missing = self.axum_missing_required("query parameter", p.name());
+        match array_item_type(p.schema_type()) {
+            Some(item_type) if p.collection_format() != Some(CollectionFormat::Multi) => {
+                let separator = quoted(collection_format_separator(
+                    p.collection_format().unwrap_or(CollectionFormat::Csv),
+                ));
+                if p.required() {
+                    quote! {
+                        let $(p.name()): Vec<$(item_type)> = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                            Some((_, v)) => match v.split($(separator)).map(|s| s.parse()).collect() {
+                                Ok(v) => v,
+                                Err(_) => return $(bad_request),
+                            },
+                            None => return $(missing),
+                        };
+                    }
+                } else {
+                    quote! {
+                        let $(p.name()): Option<Vec<$(item_type)>> = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                            Some((_, v)) => match v.split($(separator)).map(|s| s.parse()).collect() {
+                                Ok(v) => Some(v),
+                                Err(_) => return $(bad_request),
+                            },
+                            None => None,
+                        };
+                    }
+                }
+            }
+            Some(item_type) => {
+                if p.required() {
+                    quote! {
+                        let $(format!("{}_values", p.name())): Vec<&str> = query_pairs.iter().filter(|(k, _)| k.as_str() == $(&name)).map(|(_, v)| v.as_str()).collect();
+                        if $(format!("{}_values", p.name())).is_empty() {
+                            return $(missing);
+                        }
+                        let $(p.name()): Vec<$(item_type)> = match $(format!("{}_values", p.name())).iter().map(|v| v.parse()).collect() {
+                            Ok(v) => v,
+                            Err(_) => return $(bad_request),
+                        };
+                    }
+                } else {
+                    quote! {
+                        let $(format!("{}_values", p.name())): Vec<&str> = query_pairs.iter().filter(|(k, _)| k.as_str() == $(&name)).map(|(_, v)| v.as_str()).collect();
+                        let $(p.name()): Option<Vec<$(item_type)>> = if $(format!("{}_values", p.name())).is_empty() {
+                            None
+                        } else {
+                            match $(format!("{}_values", p.name())).iter().map(|v| v.parse()).collect() {
+                                Ok(v) => Some(v),
+                                Err(_) => return $(bad_request),
+                            }
+                        };
+                    }
+                }
+            }
+            None if p.required() => quote! {
+                let $(p.name()): $(p.schema_type()) = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                    Some((_, v)) => match v.parse() {
+                        Ok(v) => v,
+                        Err(_) => return $(bad_request),
+                    },
+                    None => return $(missing),
+                };
+            },
+            None => quote! {
+                let $(p.name()): Option<$(p.schema_type())> = match query_pairs.iter().find(|(k, _)| k.as_str() == $(&name)) {
+                    Some((_, v)) => match v.parse() {
+                        Ok(v) => Some(v),
+                        Err(_) => return $(bad_request),
+                    },
+                    None => None,
+                };
+            },
+        }
+    }
+
+    fn axum_header_extract(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        let bad_request = self.axum_bad_request("header", p.name());
+        if p.required() {
+            let missing = self.axum_missing_required("header", p.name());
+            quote! {
+                let $(p.name()): $(p.schema_type()) = match headers.get($(&name)).and_then(|v| v.to_str().ok()) {
+                    Some(v) => match v.parse() {
+                        Ok(v) => v,
+                        Err(_) => return $(bad_request),
+                    },
+                    None => return $(missing),
+                };
+            }
+        } else {
+            quote! {
+                let $(p.name()): Option<$(p.schema_type())> = match headers.get($(&name)).and_then(|v| v.to_str().ok()) {
+                    Some(v) => match v.parse() {
+                        Ok(v) => Some(v),
+                        Err(_) => return $(bad_request),
+                    },
+                    None => None,
+                };
+            }
+        }
+    }
+
+    fn axum_cookie_extract(&self, p: &ParameterDef) -> Tokens {
+        let name = quoted(p.name().to_owned());
+        let bad_request = self.axum_bad_request("cookie", p.name());
+        if p.required() {
+            let missing = self.axum_missing_required("cookie", p.name());
+            quote! {
+                let $(p.name()): $(p.schema_type()) = match cookies.get($(&name)) {
+                    Some(v) => match v.parse() {
+                        Ok(v) => v,
+                        Err(_) => return $(bad_request),
+                    },
+                    None => return $(missing),
+                };
+            }
+        } else {
+            quote! {
+                let $(p.name()): Option<$(p.schema_type())> = match cookies.get($(&name)) {
+                    Some(v) => match v.parse() {
+                        Ok(v) => Some(v),
+                        Err(_) => return $(bad_request),
+                    },
+                    None => None,
+                };
+            }
+        }
+    }
+
+    fn axum_into_response(&self, o: &OperationDef) -> Tokens {
+        if o.responses().len() == 1 {
+            let (status_code, _) = o.responses().first_key_value().expect("length 1");
+            let status_code_num = default_status_for(status_code);
+            if o.response() == "()" {
+                quote! {
+                    axum::http::StatusCode::from_u16($(status_code_num)).expect("valid status code").into_response()
+                }
+            } else {
+                let content_type = o.response_media_type(status_code).unwrap_or("application/json");
+                let content_type_quoted = quoted(content_type.to_owned());
+                let body = self.axum_encode_response(content_type, quote!(response));
+                quote! {
+                    if !accept_allows(headers.get(axum::http::header::ACCEPT).and_then(|v| v.to_str().ok()).unwrap_or(""), $(&content_type_quoted)) {
+                        axum::http::StatusCode::NOT_ACCEPTABLE.into_response()
+                    } else {
+                        (axum::http::StatusCode::from_u16($(status_code_num)).expect("valid status code"), $(body)).into_response()
+                    }
+                }
+            }
+        } else {
+            let match_arms: Vec<_> = o.responses().iter().map(|(status_code, schema)| {
+                let variant_name = response_variant_name(status_code);
+                let is_dynamic = is_dynamic_response_status(status_code);
+                let status_expr: Tokens = if is_dynamic {
+                    quote! { s }
+                } else {
+                    let status_code_i: u16 = status_code.parse().unwrap_or(500);
+                    quote! { $(status_code_i) }
+                };
+                let content_type = o.response_media_type(status_code).unwrap_or("application/json");
+                let content_type_quoted = quoted(content_type.to_owned());
+                let body = self.axum_encode_response(content_type, quote!(body));
+                quote! {
+                    $(o.response())::$(&variant_name)
+                    $(if is_dynamic || schema.is_some() {
+                        ($(if is_dynamic { s$(if schema.is_some() { , }) }) $(if schema.is_some() { body }))
+                    }) => {
+                        $(if schema.is_some() {
+                            if !accept_allows(headers.get(axum::http::header::ACCEPT).and_then(|v| v.to_str().ok()).unwrap_or(""), $(&content_type_quoted)) {
+                                axum::http::StatusCode::NOT_ACCEPTABLE.into_response()
+                            } else {
+                                (axum::http::StatusCode::from_u16($(&status_expr)).unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR), $(body)).into_response()
+                            }
+                        } else {
+                            (axum::http::StatusCode::from_u16($(&status_expr)).unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR), ().into_response()).into_response()
+                        })
+                    },
+                }
+            }).collect();
+            quote! {
+                match response {
+                    $(for match_arm in &match_arms => $(match_arm))
+                    $(o.response())::Unknown(s, body) => {
+                        (axum::http::StatusCode::from_u16(s).unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR), axum::Json(body)).into_response()
+                    },
+                }
+            }
+        }
+    }
+
 }
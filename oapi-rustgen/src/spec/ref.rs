@@ -145,3 +145,17 @@ impl FromStr for Ref {
 pub trait FromRef: Clone {
     fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError>;
 }
+
+/// Splits a `$ref` value of the form `source#/components/type/name` into its `(source,
+/// type, name)` parts without validating `type` against [`RefType`], so the bundler can
+/// work with component kinds it doesn't otherwise know about. Returns `None` for `$ref`s
+/// that don't follow the OpenAPI components shape (e.g. arbitrary JSON pointers).
+pub(crate) fn split_ref(path: &str) -> Option<(String, String, String)> {
+    RE_REF.captures(path).map(|parts| {
+        (
+            parts["source"].to_owned(),
+            parts["type"].to_owned(),
+            parts["name"].to_owned(),
+        )
+    })
+}
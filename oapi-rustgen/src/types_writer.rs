@@ -4,9 +4,9 @@ use jsonptr::Resolve;
 use std::collections::HashSet;
 
 use crate::{
-    analyzer::{AnalysisResult, CollectedSchema},
+    analyzer::{is_dynamic_response_status, response_variant_name, AnalysisResult, CollectedSchema},
     join_ptr,
-    spec::{ObjectOrReference, Schema, SchemaType},
+    spec::{AdditionalProperties, ObjectOrReference, Schema, SchemaType},
 };
 
 struct PropertyDef {
@@ -51,8 +51,16 @@ impl<'a> TypesWriter<'a> {
                     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
                     pub enum $(o.response()) {
                         $(for (status, r) in o.responses() join (, ) =>
-                            S$(status)$(if let Some(r) = r {($(r))})
-                        )
+                            $(response_variant_name(status))
+                            $(if is_dynamic_response_status(status) {
+                                (u16$(if let Some(r) = r {, $(r)}))
+                            } else if let Some(r) = r {
+                                ($(r))
+                            })
+                        ),
+                        /// A status code the spec doesn't declare, for handlers that need to
+                        /// respond outside the documented contract.
+                        Unknown(u16, serde_json::Value)
                     }
                 };
                 tokens.append(&enum_def);
@@ -137,6 +145,23 @@ impl<'a> TypesWriter<'a> {
             return;
         }
         if !schema.one_of.is_empty() {
+            if let Some(discriminator) = ty.discriminator() {
+                tokens.append(quote! {
+                    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+                    #[serde(tag = $(quoted(discriminator.property_name())))]
+                    pub enum $(ty.name()) {
+                        $(for v in discriminator.variants() =>
+                            $(if v.variant_ident() != v.tag_value() {
+                                #[serde(rename = $(quoted(v.tag_value())))]
+                            })
+                            $(v.variant_ident())($(v.type_name())),
+                        )
+                    }
+                });
+                tokens.line();
+                return;
+            }
+
             let composite_defs = schema.one_of.iter().enumerate().map(|(idx, schema)| {
                 let ptr = join_ptr!(ty.location(), "allOf", idx.to_string());
                 CompositeDef {
@@ -182,15 +207,24 @@ impl<'a> TypesWriter<'a> {
                 }
             })
             .collect();
+        let has_catch_all_field = matches!(
+            schema.additional_properties,
+            Some(AdditionalProperties::Any(true))
+        );
         let struct_def: Tokens = quote! {
             #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
             pub struct $(ty.name()) {
-                $(for p in properties join (, ) =>
+                $(for p in &properties join (, ) =>
                     $(if p.name != p.json_name {
                         #[serde(rename = $(quoted(p.json_name)))]
                     })
-                    pub $(p.name): $(p.ptype)
+                    pub $(&p.name): $(&p.ptype)
                 )
+                $(if has_catch_all_field {
+                    $(if !properties.is_empty() { , })
+                    #[serde(flatten)]
+                    pub other_fields: std::collections::HashMap<String, serde_json::Value>
+                })
             }
         };
         tokens.append(&struct_def);
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+
+use derive_more::{Display, Error};
+use genco::{prelude::rust::Tokens, quote, tokens::quoted};
+use jsonptr::Resolve;
+
+use crate::{
+    analyzer::{AnalysisResult, CollectedSchema},
+    spec::{ObjectOrReference, Schema, SchemaType},
+};
+
+#[derive(Debug, Display, Error)]
+pub enum ValidationWriterError {}
+
+/// Generates a `Validate` trait and, for every collected struct, an impl that checks the
+/// JSON-Schema constraint keywords (`minimum`/`maximum`, `minLength`/`maxLength`, `pattern`,
+/// `minItems`/`maxItems`, `enum`) the analyzer ignored when only deriving the field's Rust type.
+/// Every violated constraint is collected with a JSON-pointer-style field path rather than
+/// failing on the first one.
+pub struct ValidationWriter<'a> {
+    analysis: &'a AnalysisResult,
+}
+
+impl<'a> ValidationWriter<'a> {
+    pub fn new(analysis: &'a AnalysisResult) -> Self {
+        ValidationWriter { analysis }
+    }
+
+    pub fn write(&self) -> Result<Tokens, ValidationWriterError> {
+        let mut tokens = Tokens::new();
+
+        tokens.append(quote! {
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct ValidationError {
+                pub path: String,
+                pub message: String,
+            }
+
+            pub trait Validate {
+                fn validate(&self) -> Result<(), Vec<ValidationError>>;
+            }
+        });
+        tokens.line();
+
+        let spec_value =
+            serde_json::to_value(self.analysis.spec()).expect("schema should be serializable");
+        for ty in self.analysis.schemas() {
+            let schema = spec_value
+                .resolve(ty.location())
+                .expect("types to check should be resolvable");
+            let schema: ObjectOrReference<Schema> =
+                serde_json::from_value(schema.clone()).expect("should be a schema");
+            if let ObjectOrReference::Object(schema) = &schema {
+                if schema.schema_type == Some(SchemaType::Object) {
+                    tokens.append(&self.write_validate_impl(ty, schema));
+                    tokens.line();
+                }
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    fn write_validate_impl(&self, ty: &CollectedSchema, schema: &Schema) -> Tokens {
+        let required_properties: HashSet<_> = schema.required.iter().collect();
+        let field_checks: Vec<_> = schema
+            .properties
+            .iter()
+            .map(|(json_name, prop_schema)| {
+                let field_name = self.analysis.renamer().name_property(json_name);
+                let is_required = required_properties.contains(json_name);
+                self.write_field_check(json_name, &field_name, self.resolve_schema(prop_schema), is_required)
+            })
+            .collect();
+
+        quote! {
+            impl Validate for $(ty.name()) {
+                fn validate(&self) -> Result<(), Vec<ValidationError>> {
+                    let mut errors = vec![];
+                    $(for c in field_checks => $(c))
+                    if errors.is_empty() {
+                        Ok(())
+                    } else {
+                        Err(errors)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves `schema` to its underlying `Schema`, following a `$ref` via
+    /// `AnalysisResult::find_schema` the way the analyzer itself does. A property declared via
+    /// `$ref` (the common case for anything pulled out into `components/schemas`) has no inline
+    /// `Object` to read constraints off of -- skipping it there would silently validate nothing.
+    fn resolve_schema<'s>(&self, schema: &'s ObjectOrReference<Schema>) -> Option<&'s Schema> {
+        match schema {
+            ObjectOrReference::Object(schema) => Some(schema),
+            ObjectOrReference::Ref { ref_path } => {
+                self.analysis.find_schema(ref_path).map(|ty| ty.schema())
+            }
+        }
+    }
+
+    /// Whether `schema` is an `object` schema that actually gets a generated struct (and thus a
+    /// `Validate` impl) -- a property-less object (e.g. an `additionalProperties` map) has no
+    /// struct to recurse into.
+    fn is_validated_object(&self, schema: &Schema) -> bool {
+        schema.schema_type == Some(SchemaType::Object) && !schema.properties.is_empty()
+    }
+
+    fn write_field_check(
+        &self,
+        json_name: &str,
+        field_name: &str,
+        prop_schema: Option<&Schema>,
+        is_required: bool,
+    ) -> Tokens {
+        let Some(prop_schema) = prop_schema else {
+            return Tokens::new();
+        };
+
+        let constraints = self.write_constraints("value", json_name, prop_schema);
+        if constraints.is_empty() {
+            return Tokens::new();
+        }
+
+        if is_required {
+            quote! {
+                { let value = &self.$(field_name.to_owned()); $(constraints) }
+            }
+        } else {
+            quote! {
+                if let Some(value) = &self.$(field_name.to_owned()) { $(constraints) }
+            }
+        }
+    }
+
+    fn write_constraints(&self, value_expr: &str, json_name: &str, schema: &Schema) -> Tokens {
+        let mut tokens = Tokens::new();
+        let path = quoted(format!("/{}", json_name.replace('~', "~0").replace('/', "~1")));
+
+        if let Some(minimum) = schema.minimum {
+            let exclusive = schema.exclusive_minimum.unwrap_or(false);
+            let op = if exclusive { "<=" } else { "<" };
+            tokens.append(quote! {
+                if (*$(value_expr.to_owned()) as f64) $(op.to_owned()) $(minimum) {
+                    errors.push(ValidationError { path: $(&path), message: format!("value {} is below the minimum of {}", $(value_expr.to_owned()), $(minimum)) });
+                }
+            });
+        }
+        if let Some(maximum) = schema.maximum {
+            let exclusive = schema.exclusive_maximum.unwrap_or(false);
+            let op = if exclusive { ">=" } else { ">" };
+            tokens.append(quote! {
+                if (*$(value_expr.to_owned()) as f64) $(op.to_owned()) $(maximum) {
+                    errors.push(ValidationError { path: $(&path), message: format!("value {} is above the maximum of {}", $(value_expr.to_owned()), $(maximum)) });
+                }
+            });
+        }
+        if let Some(min_length) = schema.min_length {
+            tokens.append(quote! {
+                if $(value_expr.to_owned()).chars().count() < $(min_length) {
+                    errors.push(ValidationError { path: $(&path), message: format!("length is below the minimum of {}", $(min_length)) });
+                }
+            });
+        }
+        if let Some(max_length) = schema.max_length {
+            tokens.append(quote! {
+                if $(value_expr.to_owned()).chars().count() > $(max_length) {
+                    errors.push(ValidationError { path: $(&path), message: format!("length is above the maximum of {}", $(max_length)) });
+                }
+            });
+        }
+        if let Some(pattern) = &schema.pattern {
+            tokens.append(quote! {
+                {
+                    static RE: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| regex::Regex::new($(quoted(pattern.to_owned()))).expect("pattern should compile"));
+                    if !RE.is_match($(value_expr.to_owned())) {
+                        errors.push(ValidationError { path: $(&path), message: "value does not match the required pattern".to_owned() });
+                    }
+                }
+            });
+        }
+        if let Some(min_items) = schema.min_items {
+            tokens.append(quote! {
+                if $(value_expr.to_owned()).len() < $(min_items) {
+                    errors.push(ValidationError { path: $(&path), message: format!("item count is below the minimum of {}", $(min_items)) });
+                }
+            });
+        }
+        if let Some(max_items) = schema.max_items {
+            tokens.append(quote! {
+                if $(value_expr.to_owned()).len() > $(max_items) {
+                    errors.push(ValidationError { path: $(&path), message: format!("item count is above the maximum of {}", $(max_items)) });
+                }
+            });
+        }
+        if !schema.enum_values.is_empty() {
+            let allowed: Vec<_> = schema
+                .enum_values
+                .iter()
+                .map(|v| serde_json::to_string(v).expect("should serialize"))
+                .collect();
+            tokens.append(quote! {
+                if !serde_json::to_string($(value_expr.to_owned())).map(|v| [$(for a in &allowed join (, ) => $(quoted(a.to_owned())))].contains(&v.as_str())).unwrap_or(false) {
+                    errors.push(ValidationError { path: $(&path), message: "value is not one of the allowed values".to_owned() });
+                }
+            });
+        }
+        let item_is_validated_object = schema
+            .items
+            .as_deref()
+            .and_then(|items| self.resolve_schema(items))
+            .map(|item_schema| self.is_validated_object(item_schema))
+            .unwrap_or(false);
+        if schema.schema_type == Some(SchemaType::Array) && item_is_validated_object {
+            tokens.append(quote! {
+                for (i, item) in $(value_expr.to_owned()).iter().enumerate() {
+                    if let Err(item_errors) = item.validate() {
+                        errors.extend(item_errors.into_iter().map(|e| ValidationError {
+                            path: format!("{}/{}{}", $(&path), i, e.path),
+                            message: e.message,
+                        }));
+                    }
+                }
+            });
+        }
+        if self.is_validated_object(schema) {
+            tokens.append(quote! {
+                if let Err(nested_errors) = $(value_expr.to_owned()).validate() {
+                    errors.extend(nested_errors.into_iter().map(|e| ValidationError {
+                        path: format!("{}{}", $(&path), e.path),
+                        message: e.message,
+                    }));
+                }
+            });
+        }
+
+        tokens
+    }
+}